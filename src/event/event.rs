@@ -89,12 +89,16 @@ impl Event {
     /// | [OS selector] | Flag(s) checked |
     /// |---------------|-----------------|
     /// | [epoll]       | `EPOLLHUP`, or  |
-    /// |               | `EPOLLIN` and `EPOLLRDHUP` |
+    /// |               | `EPOLLRDHUP`    |
     /// | [kqueue]      | `EV_EOF`        |
     ///
+    /// To only receive events when the read half is closed, and not every
+    /// time the source becomes readable, use [`Interest::READ_CLOSED`].
+    ///
     /// [OS selector]: ../struct.Poll.html#implementation-notes
     /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
     /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [`Interest::READ_CLOSED`]: crate::Interest::READ_CLOSED
     pub fn is_read_closed(&self) -> bool {
         sys::event::is_read_closed(&self.inner)
     }
@@ -183,6 +187,44 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns a platform specific view of the event as returned by the OS.
+    ///
+    /// This gives access to details that are not exposed by the portable
+    /// methods, such as the exact `epoll(7)` flags or the `data` field of a
+    /// `kevent` (e.g. the number of bytes available to read). See
+    /// [`RawEvent`] for what is available on the current platform.
+    ///
+    /// [`RawEvent`]: crate::unix::RawEvent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(target_os = "linux")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    ///
+    /// for event in events.iter() {
+    ///     let raw = event.raw();
+    ///     if raw.events() & libc::EPOLLRDHUP as u32 != 0 {
+    ///         println!("peer closed its writing half: {:?}", raw);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(target_os = "linux"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(all(unix, feature = "os-poll", feature = "os-ext"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-poll", feature = "os-ext"))))]
+    pub fn raw(&self) -> &crate::unix::RawEvent {
+        crate::unix::RawEvent::from_sys_event_ref(&self.inner)
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -70,6 +70,11 @@ use std::io;
 ///         // Delegate the `deregister` call to `socket`
 ///         self.socket.deregister(registry)
 ///     }
+///
+///     fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+///         // Delegate the `pause` call to `socket`
+///         self.socket.pause(registry, token)
+///     }
 /// }
 /// ```
 pub trait Source {
@@ -109,6 +114,25 @@ pub trait Source {
     ///
     /// [`Registry::deregister`]: ../struct.Registry.html#method.deregister
     fn deregister(&mut self, registry: &Registry) -> io::Result<()>;
+
+    /// Pause the delivery of events for `self`, keeping the registration with
+    /// the given `Registry` instance.
+    ///
+    /// This function should not be called directly. Use [`Registry::pause`]
+    /// instead. Implementors should handle pausing by delegating the call to
+    /// another `Source` type.
+    ///
+    /// The default implementation returns an error of the kind
+    /// [`Unsupported`]. This means that types wrapping another `Source` must
+    /// forward this call to it, like `register` and friends, otherwise
+    /// [`Registry::pause`] fails for them.
+    ///
+    /// [`Registry::pause`]: crate::Registry::pause
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        let _ = (registry, token);
+        Err(io::ErrorKind::Unsupported.into())
+    }
 }
 
 impl<T> Source for Box<T>
@@ -136,4 +160,8 @@ where
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         (**self).deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        (**self).pause(registry, token)
+    }
 }
@@ -23,13 +23,23 @@ const WRITABLE: u8 = 0b0010;
 const AIO: u8 = 0b0100;
 const LIO: u8 = 0b1000;
 const PRIORITY: u8 = 0b10000;
+const READ_CLOSED: u8 = 0b100000;
+const ERROR: u8 = 0b1000000;
+
+/// `NonZeroU8::new(value).unwrap()` usable in constants.
+const fn non_zero(value: u8) -> NonZeroU8 {
+    match NonZeroU8::new(value) {
+        Some(value) => value,
+        None => unreachable!(),
+    }
+}
 
 impl Interest {
     /// Returns a `Interest` set representing readable interests.
-    pub const READABLE: Interest = Interest(unsafe { NonZeroU8::new_unchecked(READABLE) });
+    pub const READABLE: Interest = Interest(non_zero(READABLE));
 
     /// Returns a `Interest` set representing writable interests.
-    pub const WRITABLE: Interest = Interest(unsafe { NonZeroU8::new_unchecked(WRITABLE) });
+    pub const WRITABLE: Interest = Interest(non_zero(WRITABLE));
 
     /// Returns a `Interest` set representing AIO completion interests.
     #[cfg(any(
@@ -40,15 +50,50 @@ impl Interest {
         target_os = "tvos",
         target_os = "watchos",
     ))]
-    pub const AIO: Interest = Interest(unsafe { NonZeroU8::new_unchecked(AIO) });
+    pub const AIO: Interest = Interest(non_zero(AIO));
 
     /// Returns a `Interest` set representing LIO completion interests.
     #[cfg(target_os = "freebsd")]
-    pub const LIO: Interest = Interest(unsafe { NonZeroU8::new_unchecked(LIO) });
+    pub const LIO: Interest = Interest(non_zero(LIO));
 
     /// Returns a `Interest` set representing priority completion interests.
     #[cfg(any(target_os = "linux", target_os = "android"))]
-    pub const PRIORITY: Interest = Interest(unsafe { NonZeroU8::new_unchecked(PRIORITY) });
+    pub const PRIORITY: Interest = Interest(non_zero(PRIORITY));
+
+    /// Returns a `Interest` set representing read closed interests.
+    ///
+    /// This can be used to get notified when the peer closes its writing half
+    /// of the connection, or the connection is closed or reset, without
+    /// receiving an event every time the I/O source becomes readable. For
+    /// example to detect a peer disconnecting while holding on to an idle
+    /// socket.
+    ///
+    /// [`READABLE`] interest already implies this interest.
+    ///
+    /// [`READABLE`]: Interest::READABLE
+    ///
+    /// # Notes
+    ///
+    /// On platforms that use kqueue this uses the `EVFILT_READ` filter with
+    /// `NOTE_LOWAT` set to a large value, which means readable events may
+    /// still be returned, e.g. once the receive buffer is full.
+    pub const READ_CLOSED: Interest = Interest(non_zero(READ_CLOSED));
+
+    /// Returns a `Interest` set representing error interests.
+    ///
+    /// This can be used to get notified only of errors and the connection
+    /// being closed (hang-up), without receiving readable or writable events.
+    ///
+    /// # Notes
+    ///
+    /// Errors are always reported, regardless of the interests used.
+    ///
+    /// On platforms that use kqueue there is no filter for only errors, so
+    /// this uses the same filter as [`READ_CLOSED`], meaning events are also
+    /// returned when the read half is closed.
+    ///
+    /// [`READ_CLOSED`]: Interest::READ_CLOSED
+    pub const ERROR: Interest = Interest(non_zero(ERROR));
 
     /// Add together two `Interest`.
     ///
@@ -115,6 +160,16 @@ impl Interest {
     pub const fn is_priority(self) -> bool {
         (self.0.get() & PRIORITY) != 0
     }
+
+    /// Returns true if `Interest` contains read closed readiness.
+    pub const fn is_read_closed(self) -> bool {
+        (self.0.get() & READ_CLOSED) != 0
+    }
+
+    /// Returns true if `Interest` contains error readiness.
+    pub const fn is_error(self) -> bool {
+        (self.0.get() & ERROR) != 0
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -187,6 +242,20 @@ impl fmt::Debug for Interest {
                 one = true
             }
         }
+        if self.is_read_closed() {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "READ_CLOSED")?;
+            one = true
+        }
+        if self.is_error() {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "ERROR")?;
+            one = true
+        }
         debug_assert!(one, "printing empty interests");
         Ok(())
     }
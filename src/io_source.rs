@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 #[cfg(unix)]
 use std::os::unix::io::AsRawFd;
@@ -5,12 +6,13 @@ use std::os::unix::io::AsRawFd;
 use std::os::wasi::io::AsRawFd;
 #[cfg(windows)]
 use std::os::windows::io::AsRawSocket;
-#[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::{fmt, io};
+use std::sync::{Arc, Mutex, Weak};
+use std::{fmt, io, ptr};
 
+use crate::event::Event;
 use crate::sys::IoSourceState;
-use crate::{event, Interest, Registry, Token};
+use crate::{event, Events, Interest, Registry, Token};
 
 /// Adapter for a [`RawFd`] or [`RawSocket`] providing an [`event::Source`]
 /// implementation.
@@ -62,6 +64,9 @@ use crate::{event, Interest, Registry, Token};
 pub struct IoSource<T> {
     state: IoSourceState,
     inner: T,
+    readiness: Arc<ReadinessCache>,
+    /// Token the `readiness` is registered with in the `ReadinessTable`.
+    token: Option<Token>,
     #[cfg(debug_assertions)]
     selector_id: SelectorId,
 }
@@ -72,6 +77,8 @@ impl<T> IoSource<T> {
         IoSource {
             state: IoSourceState::new(),
             inner: io,
+            readiness: Arc::new(ReadinessCache::new()),
+            token: None,
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
         }
@@ -94,6 +101,54 @@ impl<T> IoSource<T> {
         self.state.do_io(f, &self.inner)
     }
 
+    /// Same as [`do_io`], but also updates the cached readiness: if the
+    /// operation returns a [`WouldBlock`] error `interest` is removed from it.
+    ///
+    /// `interest` should be [`Interest::READABLE`] for operations that read
+    /// from (or accept on) the I/O source and [`Interest::WRITABLE`] for
+    /// operations that write to it.
+    ///
+    /// [`do_io`]: IoSource::do_io
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn do_io_with<F, R>(&self, interest: Interest, f: F) -> io::Result<R>
+    where
+        F: FnOnce(&T) -> io::Result<R>,
+    {
+        let tick = self.readiness.tick();
+        let res = self.do_io(f);
+        if let Err(ref err) = res {
+            if err.kind() == io::ErrorKind::WouldBlock {
+                self.readiness.clear(interest, tick);
+            }
+        }
+        res
+    }
+
+    /// Returns the last known readiness of the I/O source.
+    ///
+    /// See [`ReadinessCache`] for how this is determined.
+    pub fn readiness(&self) -> Option<Interest> {
+        self.readiness.get()
+    }
+
+    /// Registers the readiness with `registry` under `token`, so that
+    /// [`Poll::poll`] updates it.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    fn set_token(&mut self, registry: &Registry, token: Token) {
+        registry
+            .readiness()
+            .insert(self.token, token, &self.readiness);
+        self.token = Some(token);
+    }
+
+    /// Removes the readiness from `registry`.
+    fn remove_token(&mut self, registry: &Registry) {
+        if let Some(token) = self.token.take() {
+            registry.readiness().remove(token, &self.readiness);
+        }
+    }
+
     /// Returns the I/O source, dropping the state.
     ///
     /// # Notes
@@ -144,7 +199,9 @@ where
         self.selector_id.associate(registry)?;
         registry
             .selector()
-            .register(self.inner.as_raw_fd(), token, interests)
+            .register(self.inner.as_raw_fd(), token, interests)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
     fn reregister(
@@ -157,13 +214,25 @@ where
         self.selector_id.check_association(registry)?;
         registry
             .selector()
-            .reregister(self.inner.as_raw_fd(), token, interests)
+            .reregister(self.inner.as_raw_fd(), token, interests)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(registry)?;
-        registry.selector().deregister(self.inner.as_raw_fd())
+        registry.selector().deregister(self.inner.as_raw_fd())?;
+        self.remove_token(registry);
+        Ok(())
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        #[cfg(debug_assertions)]
+        self.selector_id.check_association(registry)?;
+        registry.selector().pause(self.inner.as_raw_fd(), token)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 }
 
@@ -181,7 +250,9 @@ where
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
         self.state
-            .register(registry, token, interests, self.inner.as_raw_socket())
+            .register(registry, token, interests, self.inner.as_raw_socket())?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
     fn reregister(
@@ -192,13 +263,25 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.check_association(registry)?;
-        self.state.reregister(registry, token, interests)
+        self.state.reregister(registry, token, interests)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
-    fn deregister(&mut self, _registry: &Registry) -> io::Result<()> {
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
-        self.selector_id.remove_association(_registry)?;
-        self.state.deregister()
+        self.selector_id.remove_association(registry)?;
+        self.state.deregister()?;
+        self.remove_token(registry);
+        Ok(())
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        #[cfg(debug_assertions)]
+        self.selector_id.check_association(registry)?;
+        self.state.pause(registry, token)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 }
 
@@ -217,7 +300,9 @@ where
         self.selector_id.associate(registry)?;
         registry
             .selector()
-            .register(self.inner.as_raw_fd() as _, token, interests)
+            .register(self.inner.as_raw_fd() as _, token, interests)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
     fn reregister(
@@ -230,13 +315,17 @@ where
         self.selector_id.check_association(registry)?;
         registry
             .selector()
-            .reregister(self.inner.as_raw_fd() as _, token, interests)
+            .reregister(self.inner.as_raw_fd() as _, token, interests)?;
+        self.set_token(registry, token);
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(registry)?;
-        registry.selector().deregister(self.inner.as_raw_fd() as _)
+        registry.selector().deregister(self.inner.as_raw_fd() as _)?;
+        self.remove_token(registry);
+        Ok(())
     }
 }
 
@@ -249,6 +338,178 @@ where
     }
 }
 
+/// Cache of the last known readiness of an `IoSource`.
+///
+/// Because all registrations are edge-triggered the user has to keep track of
+/// whether or not an I/O source hit a `WouldBlock` error since the last event.
+/// This does that for them: the readiness starts out as readable and writable
+/// (we simply don't know), an operation returning `WouldBlock` clears the
+/// readiness for that operation and events returned by `Poll::poll` set it
+/// again.
+///
+/// Next to the readiness bits this keeps a tick, which is incremented for
+/// every event. A `WouldBlock` error only clears the readiness if no event
+/// was received while the operation was running, otherwise we could lose the
+/// readiness set by an event that arrived after the system call returned, but
+/// before the readiness was cleared.
+#[derive(Debug)]
+struct ReadinessCache(AtomicUsize);
+
+// These must be unique.
+const READABLE: usize = 0b01;
+const WRITABLE: usize = 0b10;
+/// Mask for the readiness bits, the remaining bits are the tick.
+const READINESS: usize = READABLE | WRITABLE;
+/// A single tick, added to the value for each event.
+const TICK: usize = READINESS + 1;
+
+impl ReadinessCache {
+    const fn new() -> ReadinessCache {
+        ReadinessCache(AtomicUsize::new(READABLE | WRITABLE))
+    }
+
+    fn get(&self) -> Option<Interest> {
+        let readiness = self.0.load(Ordering::Acquire);
+        match (readiness & READABLE != 0, readiness & WRITABLE != 0) {
+            (true, true) => Some(Interest::READABLE | Interest::WRITABLE),
+            (true, false) => Some(Interest::READABLE),
+            (false, true) => Some(Interest::WRITABLE),
+            (false, false) => None,
+        }
+    }
+
+    /// Returns the current tick, to be passed to `clear`.
+    fn tick(&self) -> usize {
+        self.0.load(Ordering::Acquire) & !READINESS
+    }
+
+    /// Clear `interest`, unless an event was received since `tick`.
+    fn clear(&self, interest: Interest, tick: usize) {
+        let bits = to_bits(interest);
+        let _ = self
+            .0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                if current & !READINESS == tick {
+                    Some(current & !bits)
+                } else {
+                    None
+                }
+            });
+    }
+
+    fn update(&self, event: &Event) {
+        let mut readiness = 0;
+        // Errors and closed halves don't block, so we consider those ready as
+        // well.
+        if event.is_readable() || event.is_read_closed() || event.is_error() {
+            readiness |= READABLE;
+        }
+        if event.is_writable() || event.is_write_closed() || event.is_error() {
+            readiness |= WRITABLE;
+        }
+        let _ = self
+            .0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                Some(current.wrapping_add(TICK) | readiness)
+            });
+    }
+}
+
+fn to_bits(interest: Interest) -> usize {
+    let mut bits = 0;
+    if interest.is_readable() {
+        bits |= READABLE;
+    }
+    if interest.is_writable() {
+        bits |= WRITABLE;
+    }
+    bits
+}
+
+/// The `ReadinessCache`s of all `IoSource`s registered with a `Registry`,
+/// shared between all clones of it.
+///
+/// Multiple sources can be registered using the same token, so each token maps
+/// to a list of caches. The caches are weak references, as sources don't have
+/// to be deregistered before they're dropped.
+#[derive(Debug, Default)]
+pub(crate) struct ReadinessTable {
+    inner: Mutex<TableInner>,
+}
+
+#[derive(Debug, Default)]
+struct TableInner {
+    caches: HashMap<Token, Vec<Weak<ReadinessCache>>>,
+    /// Number of tokens at which to remove the caches of dropped sources.
+    prune_at: usize,
+}
+
+impl ReadinessTable {
+    /// Insert `cache` under `token`, removing it from `old_token` (if any).
+    fn insert(&self, old_token: Option<Token>, token: Token, cache: &Arc<ReadinessCache>) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(old_token) = old_token {
+            if old_token == token {
+                return;
+            }
+            remove(&mut inner.caches, old_token, cache);
+        }
+        inner
+            .caches
+            .entry(token)
+            .or_default()
+            .push(Arc::downgrade(cache));
+
+        // Sources dropped without being deregistered leave their caches
+        // behind, so once in a while we remove those.
+        if inner.caches.len() >= inner.prune_at {
+            inner.caches.retain(|_, caches| {
+                caches.retain(|cache| cache.strong_count() != 0);
+                !caches.is_empty()
+            });
+            inner.prune_at = (inner.caches.len() * 2).max(64);
+        }
+    }
+
+    /// Remove `cache` from `token`.
+    fn remove(&self, token: Token, cache: &Arc<ReadinessCache>) {
+        remove(&mut self.inner.lock().unwrap().caches, token, cache)
+    }
+
+    /// Update the caches using `events`, returned by `Poll::poll`.
+    pub(crate) fn update(&self, events: &Events) {
+        if events.is_empty() {
+            return;
+        }
+        let inner = self.inner.lock().unwrap();
+        if inner.caches.is_empty() {
+            return;
+        }
+        for event in events {
+            if let Some(caches) = inner.caches.get(&event.token()) {
+                for cache in caches.iter().filter_map(Weak::upgrade) {
+                    cache.update(event);
+                }
+            }
+        }
+    }
+}
+
+fn remove(
+    caches: &mut HashMap<Token, Vec<Weak<ReadinessCache>>>,
+    token: Token,
+    cache: &Arc<ReadinessCache>,
+) {
+    if let Some(entries) = caches.get_mut(&token) {
+        entries.retain(|entry| {
+            entry.strong_count() != 0 && !ptr::eq(entry.as_ptr(), Arc::as_ptr(cache))
+        });
+        if entries.is_empty() {
+            let _ = caches.remove(&token);
+        }
+    }
+}
+
 /// Used to associate an `IoSource` with a `sys::Selector`.
 #[cfg(debug_assertions)]
 #[derive(Debug)]
@@ -64,6 +64,8 @@ cfg_net! {
 #[doc(no_inline)]
 pub use event::Events;
 pub use interest::Interest;
+#[cfg(all(unix, feature = "os-poll"))]
+pub use poll::SharedPoll;
 pub use poll::{Poll, Registry};
 pub use token::Token;
 #[cfg(not(target_os = "wasi"))]
@@ -74,14 +76,60 @@ pub use waker::Waker;
 pub mod unix {
     //! Unix only extensions.
 
+    #[cfg(all(feature = "os-poll", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "os-poll", target_os = "linux"))))]
+    pub mod aio {
+        //! Linux native asynchronous I/O.
+        //!
+        //! See the [`Context`] type for documentation.
+
+        pub use crate::sys::aio::{Completion, Context};
+    }
+
     pub mod pipe {
         //! Unix pipe.
         //!
         //! See the [`new`] function for documentation.
 
-        pub use crate::sys::pipe::{new, Receiver, Sender};
+        pub use crate::sys::pipe::{new, Builder, Receiver, Sender};
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+        pub use crate::sys::pipe::CopyBidirectional;
+    }
+
+    pub mod process {
+        //! Child processes.
+        //!
+        //! See the [`Command`] type for documentation.
+
+        pub use crate::sys::process::{Child, Command};
+    }
+
+    pub mod stdio {
+        //! Non-blocking standard streams.
+        //!
+        //! See the [`stdin`] function for documentation.
+
+        pub use crate::sys::stdio::{stderr, stdin, stdout, Stderr, Stdin, Stdout};
     }
 
+    #[cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub use crate::sys::eventfd::EventFd;
+    #[cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub use crate::sys::inotify::{Inotify, InotifyEvent, InotifyEvents, WatchDescriptor};
+    pub use crate::sys::tty::{Tty, WindowSize};
+    #[cfg(feature = "os-poll")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "os-poll")))]
+    pub use crate::sys::RawEvent;
     pub use crate::sys::SourceFd;
 }
 
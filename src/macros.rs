@@ -69,6 +69,70 @@ macro_rules! cfg_any_os_ext {
     }
 }
 
+/// `TcpInfo` is supported, see `TcpStream::tcp_info`.
+macro_rules! cfg_tcp_info {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "tvos",
+                target_os = "watchos",
+                windows,
+            ))]
+            #[cfg_attr(
+                docsrs,
+                doc(cfg(any(
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "ios",
+                    target_os = "linux",
+                    target_os = "macos",
+                    target_os = "tvos",
+                    target_os = "watchos",
+                    windows,
+                )))
+            )]
+            $item
+        )*
+    }
+}
+
+/// `sendfile(2)` is supported, see `TcpStream::sendfile`.
+macro_rules! cfg_sendfile {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))]
+            #[cfg_attr(
+                docsrs,
+                doc(cfg(any(
+                    target_os = "android",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "ios",
+                    target_os = "linux",
+                    target_os = "macos",
+                    target_os = "tvos",
+                    target_os = "watchos",
+                )))
+            )]
+            $item
+        )*
+    }
+}
+
 macro_rules! trace {
     ($($t:tt)*) => {
         log!(trace, $($t)*)
@@ -0,0 +1,360 @@
+use crate::io_source::IoSource;
+use crate::{event, sys, Interest, Registry, Token};
+
+use std::net::IpAddr;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use std::{fmt, io};
+
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+/// Size of the ICMP (and ICMPv6) echo header.
+const ECHO_HEADER_LEN: usize = 8;
+
+/// An Internet Control Message Protocol socket.
+///
+/// Two kinds of sockets are supported:
+///
+///  * Datagram sockets, created using [`dgram_v4`] or [`dgram_v6`]. These
+///    can be used to send echo requests without special privileges. On Linux
+///    the group of the process must be allowed in the
+///    `net.ipv4.ping_group_range` sysctl and the kernel sets the identifier of
+///    echo requests, replacing the one provided.
+///  * Raw sockets, created using [`raw_v4`] or [`raw_v6`]. These receive all
+///    ICMP messages, not just the replies to this socket's requests, and
+///    usually require elevated privileges (`CAP_NET_RAW` on Linux).
+///
+/// Data is sent and received as ICMP messages, starting with the ICMP header.
+/// Depending on the OS and the kind of socket, received IPv4 packets may
+/// include the IP header. [`send_echo_request`] and [`recv_echo_reply`] deal
+/// with these differences and can be used to ping a host.
+///
+/// [`dgram_v4`]: IcmpSocket::dgram_v4
+/// [`dgram_v6`]: IcmpSocket::dgram_v6
+/// [`raw_v4`]: IcmpSocket::raw_v4
+/// [`raw_v6`]: IcmpSocket::raw_v6
+/// [`send_echo_request`]: IcmpSocket::send_echo_request
+/// [`recv_echo_reply`]: IcmpSocket::recv_echo_reply
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::IcmpSocket;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut socket = IcmpSocket::dgram_v4()?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// socket.send_echo_request("127.0.0.1".parse()?, 1, 1, b"ping")?;
+///
+/// poll.poll(&mut events, None)?;
+/// let mut buf = [0; 1024];
+/// let (reply, addr) = socket.recv_echo_reply(&mut buf)?;
+/// println!("reply from {}: seq={}", addr, reply.seq());
+/// # Ok(())
+/// # }
+/// ```
+pub struct IcmpSocket {
+    inner: IoSource<OwnedFd>,
+}
+
+impl IcmpSocket {
+    /// Creates a new IPv4 datagram ICMP socket (`SOCK_DGRAM`, `IPPROTO_ICMP`).
+    pub fn dgram_v4() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(false, libc::SOCK_DGRAM)
+    }
+
+    /// Creates a new IPv6 datagram ICMP socket (`SOCK_DGRAM`,
+    /// `IPPROTO_ICMPV6`).
+    pub fn dgram_v6() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(true, libc::SOCK_DGRAM)
+    }
+
+    /// Creates a new IPv4 raw ICMP socket (`SOCK_RAW`, `IPPROTO_ICMP`).
+    pub fn raw_v4() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(false, libc::SOCK_RAW)
+    }
+
+    /// Creates a new IPv6 raw ICMP socket (`SOCK_RAW`, `IPPROTO_ICMPV6`).
+    pub fn raw_v6() -> io::Result<IcmpSocket> {
+        IcmpSocket::new(true, libc::SOCK_RAW)
+    }
+
+    fn new(ipv6: bool, socket_type: libc::c_int) -> io::Result<IcmpSocket> {
+        sys::icmp::new(ipv6, socket_type).map(|socket| IcmpSocket {
+            inner: IoSource::new(socket),
+        })
+    }
+
+    /// Binds the socket to the local address `addr`.
+    pub fn bind(&self, addr: IpAddr) -> io::Result<()> {
+        sys::icmp::bind(&self.inner, addr)
+    }
+
+    /// Returns the local address of this socket.
+    pub fn local_addr(&self) -> io::Result<IpAddr> {
+        sys::icmp::local_addr(&self.inner)
+    }
+
+    /// Connects the socket to `addr`, setting the default destination for
+    /// [`send`] and limiting the messages received to those from `addr`.
+    ///
+    /// [`send`]: IcmpSocket::send
+    pub fn connect(&self, addr: IpAddr) -> io::Result<()> {
+        sys::icmp::connect(&self.inner, addr)
+    }
+
+    /// Sends an ICMP message to the given address. On success, returns the
+    /// number of bytes written.
+    pub fn send_to(&self, buf: &[u8], target: IpAddr) -> io::Result<usize> {
+        self.inner.do_io_with(Interest::WRITABLE, |inner| {
+            sys::icmp::send_to(inner, buf, target)
+        })
+    }
+
+    /// Sends an ICMP message to the address the socket is connected to. On
+    /// success, returns the number of bytes written.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io_with(Interest::WRITABLE, |inner| sys::icmp::send(inner, buf))
+    }
+
+    /// Receives a message from the socket. On success, returns the number of
+    /// bytes read and the address from whence the message came.
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, IpAddr)> {
+        self.inner.do_io_with(Interest::READABLE, |inner| {
+            sys::icmp::recv_from(inner, buf, 0)
+        })
+    }
+
+    /// Receives a message from the socket, without removing it from the input
+    /// queue. On success, returns the number of bytes read and the address
+    /// from whence the message came.
+    pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, IpAddr)> {
+        self.inner.do_io_with(Interest::READABLE, |inner| {
+            sys::icmp::recv_from(inner, buf, libc::MSG_PEEK)
+        })
+    }
+
+    /// Receives a message from the socket. On success, returns the number of
+    /// bytes read.
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_from(buf).map(|(n, _)| n)
+    }
+
+    /// Sends an echo request (ping) with the identifier `id`, sequence number
+    /// `seq` and `payload` to `target`.
+    ///
+    /// The checksum is calculated for ICMP, for ICMPv6 it's left to the
+    /// kernel.
+    pub fn send_echo_request(
+        &self,
+        target: IpAddr,
+        id: u16,
+        seq: u16,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        let packet = echo_request(target.is_ipv6(), id, seq, payload);
+        self.send_to(&packet, target).map(|_| ())
+    }
+
+    /// Receives an echo reply, returning it along with the address from whence
+    /// it came.
+    ///
+    /// Messages received on the socket that are not echo replies are
+    /// discarded. If no echo reply is available this returns a
+    /// [`WouldBlock`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn recv_echo_reply<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> io::Result<(IcmpEchoReply<'a>, IpAddr)> {
+        let (start, n, id, seq, addr) =
+            self.inner.do_io_with(Interest::READABLE, |inner| loop {
+                let (n, addr) = sys::icmp::recv_from(inner, buf, 0)?;
+                if let Some((start, id, seq)) = parse_echo_reply(&buf[..n], addr.is_ipv6()) {
+                    return Ok((start, n, id, seq, addr));
+                }
+            })?;
+        let reply = IcmpEchoReply {
+            id,
+            seq,
+            payload: &buf[start..n],
+        };
+        Ok((reply, addr))
+    }
+
+    /// Returns the last known readiness of the socket, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error.
+    ///
+    /// # Notes
+    ///
+    /// This method is required to be called for **all** I/O operations to
+    /// ensure the user will receive events once the socket is ready again after
+    /// returning a [`WouldBlock`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.do_io(|_| f())
+    }
+}
+
+/// An echo reply received by [`IcmpSocket::recv_echo_reply`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IcmpEchoReply<'a> {
+    id: u16,
+    seq: u16,
+    payload: &'a [u8],
+}
+
+impl<'a> IcmpEchoReply<'a> {
+    /// Returns the identifier of the reply.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the sequence number of the reply.
+    pub fn seq(&self) -> u16 {
+        self.seq
+    }
+
+    /// Returns the payload of the reply.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+}
+
+/// Create an echo request message.
+fn echo_request(ipv6: bool, id: u16, seq: u16, payload: &[u8]) -> Vec<u8> {
+    let msg_type = if ipv6 {
+        ICMPV6_ECHO_REQUEST
+    } else {
+        ICMP_ECHO_REQUEST
+    };
+    let mut packet = Vec::with_capacity(ECHO_HEADER_LEN + payload.len());
+    packet.extend_from_slice(&[msg_type, 0, 0, 0]);
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&seq.to_be_bytes());
+    packet.extend_from_slice(payload);
+    if !ipv6 {
+        let checksum = checksum(&packet);
+        packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    }
+    packet
+}
+
+/// Parses an echo reply, returning the start of the payload, the identifier
+/// and the sequence number.
+fn parse_echo_reply(mut packet: &[u8], ipv6: bool) -> Option<(usize, u16, u16)> {
+    let mut start = 0;
+    // Raw IPv4 sockets (and datagram sockets on some OSs) include the IP
+    // header. The version nibble can't be confused with an ICMP type.
+    if !ipv6 && !packet.is_empty() && packet[0] >> 4 == 4 {
+        start = usize::from(packet[0] & 0xf) * 4;
+        packet = packet.get(start..)?;
+    }
+
+    let reply_type = if ipv6 {
+        ICMPV6_ECHO_REPLY
+    } else {
+        ICMP_ECHO_REPLY
+    };
+    if packet.len() < ECHO_HEADER_LEN || packet[0] != reply_type || packet[1] != 0 {
+        return None;
+    }
+    let id = u16::from_be_bytes([packet[4], packet[5]]);
+    let seq = u16::from_be_bytes([packet[6], packet[7]]);
+    Some((start + ECHO_HEADER_LEN, id, seq))
+}
+
+/// The Internet checksum, see RFC 1071.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = data
+        .chunks(2)
+        .map(|chunk| u32::from(u16::from_be_bytes([chunk[0], *chunk.get(1).unwrap_or(&0)])))
+        .sum::<u32>();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+impl event::Source for IcmpSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
+}
+
+impl fmt::Debug for IcmpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl IntoRawFd for IcmpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for IcmpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for IcmpSocket {
+    /// Converts a `RawFd` to an `IcmpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> IcmpSocket {
+        IcmpSocket {
+            inner: IoSource::new(OwnedFd::from_raw_fd(fd)),
+        }
+    }
+}
@@ -26,6 +26,19 @@
 //! give is to always call receive with a large enough buffer.
 
 mod tcp;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+pub use self::tcp::TcpFastOpen;
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub use self::tcp::TcpKeepalive;
+#[cfg(all(unix, feature = "os-poll"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-poll"))))]
+pub use self::tcp::TcpSocket;
+#[cfg(not(target_os = "wasi"))]
+pub use self::tcp::{TcpConnectError, TcpConnector};
+cfg_tcp_info! {
+    pub use self::tcp::{TcpInfo, TcpState};
+}
 pub use self::tcp::{TcpListener, TcpStream};
 
 #[cfg(not(target_os = "wasi"))]
@@ -37,3 +50,45 @@ pub use self::udp::UdpSocket;
 mod uds;
 #[cfg(unix)]
 pub use self::uds::{SocketAddr, UnixDatagram, UnixListener, UnixStream};
+
+#[cfg(all(
+    feature = "os-poll",
+    unix,
+    not(any(target_os = "illumos", target_os = "solaris"))
+))]
+mod reuseport;
+#[cfg(all(
+    feature = "os-poll",
+    unix,
+    not(any(target_os = "illumos", target_os = "solaris"))
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "os-poll",
+        unix,
+        not(any(target_os = "illumos", target_os = "solaris"))
+    )))
+)]
+pub use self::reuseport::ReuseportGroup;
+
+#[cfg(all(feature = "os-poll", unix))]
+mod icmp;
+#[cfg(all(feature = "os-poll", unix))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "os-poll", unix))))]
+pub use self::icmp::{IcmpEchoReply, IcmpSocket};
+
+#[cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux")))]
+mod netlink;
+#[cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "os-poll", any(target_os = "android", target_os = "linux"))))
+)]
+pub use self::netlink::{NetlinkAddr, NetlinkSocket};
+
+#[cfg(target_os = "linux")]
+mod zerocopy;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::zerocopy::ZerocopyCompletion;
@@ -0,0 +1,209 @@
+use crate::io_source::IoSource;
+use crate::{event, sys, Interest, Registry, Token};
+
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use std::{fmt, io};
+
+pub use crate::sys::netlink::NetlinkAddr;
+
+/// A netlink socket, used to communicate with the Linux kernel, see
+/// [`netlink(7)`].
+///
+/// The socket uses the raw netlink protocol, messages are sent and received
+/// as bytes including the `nlmsghdr` header. Constants for the protocols,
+/// multicast groups and message types (e.g. `NETLINK_ROUTE`, `RTMGRP_LINK`
+/// and `RTM_NEWLINK`) can be found in the `libc` crate.
+///
+/// [`netlink(7)`]: https://man7.org/linux/man-pages/man7/netlink.7.html
+///
+/// # Examples
+///
+/// Receive notifications when network interfaces change.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{NetlinkAddr, NetlinkSocket};
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let addr = NetlinkAddr::new(0, libc::RTMGRP_LINK as u32);
+/// let mut socket = NetlinkSocket::bind(libc::NETLINK_ROUTE, addr)?;
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// poll.poll(&mut events, None)?;
+/// let mut buf = [0; 8192];
+/// let n = socket.recv(&mut buf)?;
+/// println!("received {} bytes of netlink messages", n);
+/// # Ok(())
+/// # }
+/// ```
+pub struct NetlinkSocket {
+    inner: IoSource<OwnedFd>,
+}
+
+impl NetlinkSocket {
+    /// Creates a netlink socket for `protocol` (e.g. `NETLINK_ROUTE`), bound
+    /// to `addr`.
+    ///
+    /// Use a port id of zero to let the kernel assign one, and set the
+    /// multicast groups to receive notifications from in the address.
+    pub fn bind(protocol: libc::c_int, addr: NetlinkAddr) -> io::Result<NetlinkSocket> {
+        sys::netlink::bind(protocol, addr).map(|socket| NetlinkSocket {
+            inner: IoSource::new(socket),
+        })
+    }
+
+    /// Returns the address of this socket, including the port id assigned by
+    /// the kernel.
+    pub fn local_addr(&self) -> io::Result<NetlinkAddr> {
+        sys::netlink::local_addr(&self.inner)
+    }
+
+    /// Join the multicast group `group`.
+    ///
+    /// Unlike the groups bit mask passed to [`bind`] this can be used for all
+    /// groups, not just the first 32. Note that `group` is the group number
+    /// (e.g. `RTNLGRP_LINK`), not a bit mask.
+    ///
+    /// [`bind`]: NetlinkSocket::bind
+    pub fn join_group(&self, group: u32) -> io::Result<()> {
+        sys::netlink::set_membership(&self.inner, group, true)
+    }
+
+    /// Leave the multicast group `group`, see [`join_group`].
+    ///
+    /// [`join_group`]: NetlinkSocket::join_group
+    pub fn leave_group(&self, group: u32) -> io::Result<()> {
+        sys::netlink::set_membership(&self.inner, group, false)
+    }
+
+    /// Sends data to the kernel.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.send_to(buf, NetlinkAddr::new(0, 0))
+    }
+
+    /// Sends data to the socket with the given address.
+    pub fn send_to(&self, buf: &[u8], addr: NetlinkAddr) -> io::Result<usize> {
+        self.inner.do_io_with(Interest::WRITABLE, |inner| {
+            sys::netlink::send_to(inner, buf, addr)
+        })
+    }
+
+    /// Receives data from the socket.
+    ///
+    /// On success, returns the number of bytes read. A single read may return
+    /// multiple netlink messages.
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_from(buf).map(|(n, _)| n)
+    }
+
+    /// Receives data from the socket.
+    ///
+    /// On success, returns the number of bytes read and the address of the
+    /// sender, which has a port id of zero for messages from the kernel.
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, NetlinkAddr)> {
+        self.inner.do_io_with(Interest::READABLE, |inner| {
+            sys::netlink::recv_from(inner, buf, 0)
+        })
+    }
+
+    /// Receives data from the socket, without removing it from the input
+    /// queue.
+    ///
+    /// On success, returns the number of bytes read and the address of the
+    /// sender.
+    pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, NetlinkAddr)> {
+        self.inner.do_io_with(Interest::READABLE, |inner| {
+            sys::netlink::recv_from(inner, buf, libc::MSG_PEEK)
+        })
+    }
+
+    /// Returns the last known readiness of the socket, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
+    /// Execute an I/O operation ensuring that the socket receives more events
+    /// if it hits a [`WouldBlock`] error.
+    ///
+    /// # Notes
+    ///
+    /// This method is required to be called for **all** I/O operations to
+    /// ensure the user will receive events once the socket is ready again after
+    /// returning a [`WouldBlock`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn try_io<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T>,
+    {
+        self.inner.do_io(|_| f())
+    }
+}
+
+impl event::Source for NetlinkSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
+}
+
+impl fmt::Debug for NetlinkSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl IntoRawFd for NetlinkSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl AsRawFd for NetlinkSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for NetlinkSocket {
+    /// Converts a `RawFd` to a `NetlinkSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> NetlinkSocket {
+        NetlinkSocket {
+            inner: IoSource::new(OwnedFd::from_raw_fd(fd)),
+        }
+    }
+}
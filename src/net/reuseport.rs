@@ -0,0 +1,144 @@
+use std::io;
+use std::net::SocketAddr;
+
+use crate::net::{TcpListener, UdpSocket};
+use crate::sys;
+
+/// Builder for a group of sockets bound to the same address using
+/// `SO_REUSEPORT`.
+///
+/// This can be used to create a listener (or UDP socket) per thread, each
+/// with its own [`Poll`] instance, all sharing the same port. How incoming
+/// connections and packets are distributed over the sockets depends on the
+/// OS: Linux distributes them using a hash of the connection, by default. Not
+/// all OSs distribute them, e.g. on macOS the last bound socket receives all
+/// traffic.
+///
+/// On Linux and Android the traffic can instead be steered to the socket
+/// matching the CPU on which the packet arrived, see [`steer_by_cpu`].
+///
+/// [`Poll`]: crate::Poll
+/// [`steer_by_cpu`]: ReuseportGroup::steer_by_cpu
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::ReuseportGroup;
+///
+/// let listeners = ReuseportGroup::new("127.0.0.1:0".parse()?, 4).tcp_listeners()?;
+/// assert_eq!(listeners.len(), 4);
+///
+/// // All listeners share the same address.
+/// let addr = listeners[0].local_addr()?;
+/// for listener in &listeners {
+///     assert_eq!(listener.local_addr()?, addr);
+/// }
+///
+/// // Move each listener to its own thread with its own `Poll`.
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReuseportGroup {
+    addr: SocketAddr,
+    size: usize,
+    backlog: u32,
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    steer_by_cpu: bool,
+}
+
+impl ReuseportGroup {
+    /// Create a new builder for a group of `size` sockets bound to `addr`.
+    ///
+    /// If the port of `addr` is zero the port picked for the first socket is
+    /// used for all sockets.
+    pub fn new(addr: SocketAddr, size: usize) -> ReuseportGroup {
+        ReuseportGroup {
+            addr,
+            size,
+            backlog: 1024,
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            steer_by_cpu: false,
+        }
+    }
+
+    /// Set the backlog of the TCP listeners, defaults to 1024.
+    pub fn backlog(mut self, backlog: u32) -> ReuseportGroup {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Steer connections and packets to the socket matching the CPU on which
+    /// they arrived (`SO_ATTACH_REUSEPORT_CBPF`).
+    ///
+    /// Socket `n` (the index in the returned `Vec`) receives the traffic
+    /// arriving on the CPUs for which `cpu % size == n`. For this to keep
+    /// traffic local the thread using socket `n` should be pinned to CPU `n`
+    /// and the group size should match the number of CPUs that handle network
+    /// interrupts. [`TcpStream::incoming_cpu`] returns the CPU of an accepted
+    /// stream.
+    ///
+    /// The kernel maps the index to the sockets in the order they joined the
+    /// group. Dropping a socket, other than the last one, moves the last socket
+    /// into its place, changing the mapping for both.
+    ///
+    /// [`TcpStream::incoming_cpu`]: crate::net::TcpStream::incoming_cpu
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn steer_by_cpu(mut self, steer_by_cpu: bool) -> ReuseportGroup {
+        self.steer_by_cpu = steer_by_cpu;
+        self
+    }
+
+    /// Create the TCP listeners.
+    pub fn tcp_listeners(&self) -> io::Result<Vec<TcpListener>> {
+        let backlog = self.backlog;
+        self.build(
+            |addr| sys::reuseport::tcp_listener(addr, backlog).map(TcpListener::from_std),
+            TcpListener::local_addr,
+        )
+    }
+
+    /// Create the UDP sockets.
+    pub fn udp_sockets(&self) -> io::Result<Vec<UdpSocket>> {
+        self.build(
+            |addr| sys::reuseport::udp_socket(addr).map(UdpSocket::from_std),
+            UdpSocket::local_addr,
+        )
+    }
+
+    fn build<S, N, A>(&self, new: N, local_addr: A) -> io::Result<Vec<S>>
+    where
+        S: std::os::unix::io::AsRawFd,
+        N: Fn(SocketAddr) -> io::Result<S>,
+        A: Fn(&S) -> io::Result<SocketAddr>,
+    {
+        if self.size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuseport group size must be larger than zero",
+            ));
+        }
+
+        let mut addr = self.addr;
+        let mut sockets = Vec::with_capacity(self.size);
+        for _ in 0..self.size {
+            let socket = new(addr)?;
+            // Use the port picked by the OS for the remaining sockets.
+            addr = local_addr(&socket)?;
+            sockets.push(socket);
+        }
+
+        // The program is shared by all sockets in the group, the index it
+        // returns selects the socket in the order they were added.
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        if self.steer_by_cpu {
+            let size = self.size.min(u32::MAX as usize) as u32;
+            sys::reuseport::steer_by_cpu(&sockets[0], size)?;
+        }
+
+        Ok(sockets)
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+use crate::net::TcpStream;
+use crate::{event, Interest, Registry, Token};
+
+/// Default delay between starting connection attempts, as recommended by
+/// RFC 8305.
+const DEFAULT_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// A non-blocking TCP connector racing connection attempts to multiple
+/// addresses, following the "Happy Eyeballs" algorithm ([RFC 8305]).
+///
+/// The addresses are sorted by alternating between IPv6 and IPv4, starting
+/// with the family of the first address. A connection attempt is started for
+/// the first address right away, the next attempt is started once an attempt
+/// failed (even if other attempts are still in progress) or the [attempt
+/// delay] has passed since the last attempt was started, whichever comes
+/// first. The first attempt to succeed wins and all other attempts are
+/// cancelled.
+///
+/// The connector is a single [`event::Source`]: register it with [write
+/// interest] and call [`connect`] after each event and each time the
+/// [`timeout`] passes. Passing the `timeout` to [`Poll::poll`] ensures the
+/// next attempt is started in time. [`connect`] takes the [`Registry`] the
+/// connector is registered with, used to register the attempts started later
+/// on.
+///
+/// [RFC 8305]: https://datatracker.ietf.org/doc/html/rfc8305
+/// [attempt delay]: TcpConnector::set_attempt_delay
+/// [write interest]: Interest::WRITABLE
+/// [`connect`]: TcpConnector::connect
+/// [`timeout`]: TcpConnector::timeout
+/// [`Poll::poll`]: crate::Poll::poll
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+/// use std::net::TcpListener;
+///
+/// use mio::net::TcpConnector;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # let listener = TcpListener::bind("127.0.0.1:0")?;
+/// # let addrs = vec!["[::1]:1".parse()?, listener.local_addr()?];
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// // For example the result of a DNS lookup.
+/// let mut connector = TcpConnector::new(addrs)?;
+/// poll.registry().register(&mut connector, Token(0), Interest::WRITABLE)?;
+///
+/// let mut stream = loop {
+///     poll.poll(&mut events, connector.timeout())?;
+///     match connector.connect(poll.registry()) {
+///         Ok(stream) => break stream,
+///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+///         Err(err) => return Err(err.into()),
+///     }
+/// };
+///
+/// // The stream is connected and not registered.
+/// poll.registry().register(&mut stream, Token(1), Interest::READABLE)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TcpConnector {
+    /// Addresses not yet attempted.
+    addrs: VecDeque<SocketAddr>,
+    /// In progress connection attempts.
+    attempts: Vec<(SocketAddr, TcpStream)>,
+    /// Errors of the failed attempts.
+    errors: Vec<(SocketAddr, io::Error)>,
+    attempt_delay: Duration,
+    /// When the last attempt was started.
+    last_attempt: Instant,
+    /// Token and interests the connector is registered with.
+    registration: Option<(Token, Interest)>,
+    /// Whether the registration is paused, see `Registry::pause`.
+    paused: bool,
+}
+
+impl TcpConnector {
+    /// Create a new connector for `addrs`, starting the first connection
+    /// attempt.
+    ///
+    /// Returns an error if `addrs` is empty, or if no attempt could be started
+    /// for any of the addresses.
+    pub fn new<I>(addrs: I) -> io::Result<TcpConnector>
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        let addrs = interleave(addrs.into_iter().collect());
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no addresses to connect to",
+            ));
+        }
+
+        let mut connector = TcpConnector {
+            addrs,
+            attempts: Vec::new(),
+            errors: Vec::new(),
+            attempt_delay: DEFAULT_ATTEMPT_DELAY,
+            last_attempt: Instant::now(),
+            registration: None,
+            paused: false,
+        };
+        connector.start_attempt(None);
+        if connector.attempts.is_empty() {
+            return Err(connector.error());
+        }
+        Ok(connector)
+    }
+
+    /// Set the delay between starting connection attempts, defaults to 250
+    /// milliseconds.
+    pub fn set_attempt_delay(&mut self, delay: Duration) {
+        self.attempt_delay = delay;
+    }
+
+    /// Returns the time until the next connection attempt should be started,
+    /// or `None` if all attempts have been started.
+    pub fn timeout(&self) -> Option<Duration> {
+        if self.addrs.is_empty() {
+            None
+        } else {
+            Some(
+                self.attempt_delay
+                    .saturating_sub(self.last_attempt.elapsed()),
+            )
+        }
+    }
+
+    /// Check the progress of the connection attempts.
+    ///
+    /// If the connector is registered `registry` must be the [`Registry`] it's
+    /// registered with, it's used to register new attempts and deregister
+    /// finished ones. Otherwise it's not used.
+    ///
+    /// Returns the connected stream of the first attempt that succeeded. The
+    /// stream is not registered, even if the connector is. All other attempts
+    /// are cancelled.
+    ///
+    /// If no attempt succeeded (yet) this returns a [`WouldBlock`] error. If
+    /// all attempts failed this returns an error containing a
+    /// [`TcpConnectError`], with the errors of all attempts.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn connect(&mut self, registry: &Registry) -> io::Result<TcpStream> {
+        // Number of failed attempts not yet replaced by a new attempt.
+        let mut failed: usize = 0;
+        loop {
+            let mut i = 0;
+            while i < self.attempts.len() {
+                match check_connected(&self.attempts[i].1) {
+                    Ok(true) => {
+                        let (addr, mut stream) = self.attempts.swap_remove(i);
+                        self.cancel_attempts(registry);
+                        if self.registration.is_some() {
+                            // The connection is established, don't drop it
+                            // just because it's still registered.
+                            if let Err(err) = registry.deregister(&mut stream) {
+                                warn!("error deregistering connection to {}: {}", addr, err);
+                            }
+                        }
+                        return Ok(stream);
+                    }
+                    Ok(false) => i += 1,
+                    Err(err) => {
+                        let (addr, mut stream) = self.attempts.swap_remove(i);
+                        if self.registration.is_some() {
+                            let _ = registry.deregister(&mut stream);
+                        }
+                        self.errors.push((addr, err));
+                        failed += 1;
+                    }
+                }
+            }
+
+            // Start the next attempt if an attempt failed or when it's time
+            // to.
+            if !self.addrs.is_empty()
+                && (failed > 0 || self.last_attempt.elapsed() >= self.attempt_delay)
+            {
+                failed = failed.saturating_sub(1);
+                self.start_attempt(Some(registry));
+                // Check the new attempt, it may have connected already.
+                continue;
+            }
+
+            return if self.attempts.is_empty() {
+                Err(self.error())
+            } else {
+                Err(io::ErrorKind::WouldBlock.into())
+            };
+        }
+    }
+
+    /// Start the next connection attempt, registering it with `registry` if
+    /// the connector is registered. If starting an attempt fails the next
+    /// address is tried until one starts or no addresses are left.
+    fn start_attempt(&mut self, registry: Option<&Registry>) {
+        while let Some(addr) = self.addrs.pop_front() {
+            let registration = registry.zip(self.registration);
+            let paused = self.paused;
+            let res = TcpStream::connect(addr).and_then(|mut stream| {
+                if let Some((registry, (token, interests))) = registration {
+                    registry.register(&mut stream, token, interests)?;
+                    if paused {
+                        registry.pause(&mut stream, token)?;
+                    }
+                }
+                Ok(stream)
+            });
+            match res {
+                Ok(stream) => {
+                    self.attempts.push((addr, stream));
+                    self.last_attempt = Instant::now();
+                    break;
+                }
+                Err(err) => self.errors.push((addr, err)),
+            }
+        }
+    }
+
+    fn cancel_attempts(&mut self, registry: &Registry) {
+        for (_, mut stream) in self.attempts.drain(..) {
+            if self.registration.is_some() {
+                let _ = registry.deregister(&mut stream);
+            }
+        }
+        self.addrs.clear();
+    }
+
+    /// Returns an error with all errors of the failed attempts.
+    fn error(&mut self) -> io::Error {
+        let errors = std::mem::take(&mut self.errors);
+        let kind = errors
+            .last()
+            .map_or(io::ErrorKind::Other, |(_, err)| err.kind());
+        io::Error::new(kind, TcpConnectError { errors })
+    }
+}
+
+/// Returns `true` if `stream` is connected, `false` if the connection is
+/// still in progress and an error if connecting failed.
+fn check_connected(stream: &TcpStream) -> io::Result<bool> {
+    if let Some(err) = stream.take_error()? {
+        return Err(err);
+    }
+    match stream.peer_addr() {
+        Ok(_) => Ok(true),
+        Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(false),
+        #[cfg(unix)]
+        Err(ref err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Sort `addrs` by alternating the address families, starting with the family
+/// of the first address (RFC 8305 section 4).
+fn interleave(addrs: Vec<SocketAddr>) -> VecDeque<SocketAddr> {
+    let first_is_ipv6 = matches!(addrs.first(), Some(addr) if addr.is_ipv6());
+    let (mut first, mut second): (VecDeque<_>, VecDeque<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_ipv6);
+    let mut sorted = VecDeque::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop_front(), second.pop_front()) {
+            (None, None) => return sorted,
+            (a, b) => sorted.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+impl event::Source for TcpConnector {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        for (_, stream) in self.attempts.iter_mut() {
+            registry.register(stream, token, interests)?;
+        }
+        self.registration = Some((token, interests));
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        for (_, stream) in self.attempts.iter_mut() {
+            registry.reregister(stream, token, interests)?;
+        }
+        self.registration = Some((token, interests));
+        self.paused = false;
+        Ok(())
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        for (_, stream) in self.attempts.iter_mut() {
+            registry.deregister(stream)?;
+        }
+        self.registration = None;
+        self.paused = false;
+        Ok(())
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        for (_, stream) in self.attempts.iter_mut() {
+            registry.pause(stream, token)?;
+        }
+        if let Some((_, interests)) = self.registration {
+            self.registration = Some((token, interests));
+        }
+        self.paused = true;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TcpConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpConnector")
+            .field("addrs", &self.addrs)
+            .field("attempts", &self.attempts)
+            .field("errors", &self.errors)
+            .field("attempt_delay", &self.attempt_delay)
+            .finish()
+    }
+}
+
+/// Error returned by [`TcpConnector::connect`] if all connection attempts
+/// failed.
+///
+/// It can be retrieved from the returned `io::Error` using
+/// [`io::Error::get_ref`] and downcasting it.
+#[derive(Debug)]
+pub struct TcpConnectError {
+    errors: Vec<(SocketAddr, io::Error)>,
+}
+
+impl TcpConnectError {
+    /// Returns the address and error of each failed attempt, in the order the
+    /// attempts failed.
+    pub fn errors(&self) -> &[(SocketAddr, io::Error)] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for TcpConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to connect to any address")?;
+        for (i, (addr, err)) in self.errors.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{} ({})", sep, addr, err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for TcpConnectError {}
@@ -0,0 +1,25 @@
+/// How [`TcpStream::connect_with_data`] started the connection.
+///
+/// [`TcpStream::connect_with_data`]: crate::net::TcpStream::connect_with_data
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TcpFastOpen {
+    /// The first `n` bytes of the data were sent in the SYN.
+    Sent(usize),
+    /// No Fast Open cookie for the address was cached, the kernel sent a SYN
+    /// without data, requesting a cookie for the next connection.
+    NoCookie,
+    /// Client side Fast Open is disabled (`net.ipv4.tcp_fastopen`), a normal
+    /// connect was started.
+    Disabled,
+}
+
+impl TcpFastOpen {
+    /// Returns the number of bytes of the data sent in the SYN, zero if the
+    /// kernel fell back to a normal handshake.
+    pub fn sent(self) -> usize {
+        match self {
+            TcpFastOpen::Sent(n) => n,
+            TcpFastOpen::NoCookie | TcpFastOpen::Disabled => 0,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+/// Information about a TCP connection, returned by [`TcpStream::tcp_info`].
+///
+/// This is filled from `TCP_INFO` on Linux, Android and FreeBSD,
+/// `TCP_CONNECTION_INFO` on Apple platforms and `SIO_TCP_INFO` on Windows.
+/// Values not provided by the OS are `None`.
+///
+/// [`TcpStream::tcp_info`]: crate::net::TcpStream::tcp_info
+#[derive(Clone, Debug)]
+pub struct TcpInfo {
+    pub(crate) state: TcpState,
+    pub(crate) rtt: Duration,
+    pub(crate) rtt_var: Option<Duration>,
+    pub(crate) congestion_window: u32,
+    pub(crate) max_segment_size: u32,
+    pub(crate) retransmits: u32,
+    pub(crate) delivery_rate: Option<u64>,
+}
+
+impl TcpInfo {
+    /// Returns the state of the connection.
+    pub fn state(&self) -> TcpState {
+        self.state
+    }
+
+    /// Returns the smoothed round trip time.
+    pub fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
+    /// Returns the round trip time variation.
+    ///
+    /// Not available on Windows.
+    pub fn rtt_var(&self) -> Option<Duration> {
+        self.rtt_var
+    }
+
+    /// Returns the congestion window in bytes.
+    pub fn congestion_window(&self) -> u32 {
+        self.congestion_window
+    }
+
+    /// Returns the maximum segment size used for sending, in bytes.
+    pub fn max_segment_size(&self) -> u32 {
+        self.max_segment_size
+    }
+
+    /// Returns the total number of retransmitted segments.
+    ///
+    /// On Apple platforms and Windows only the number of retransmitted bytes
+    /// is available, this returns that number divided by the maximum segment
+    /// size.
+    pub fn retransmits(&self) -> u32 {
+        self.retransmits
+    }
+
+    /// Returns the most recent goodput measurement in bytes per second.
+    ///
+    /// Only available on Linux 4.9 and later, and Android.
+    pub fn delivery_rate(&self) -> Option<u64> {
+        self.delivery_rate
+    }
+}
+
+/// State of a TCP connection, see [`TcpInfo::state`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TcpState {
+    /// Closed.
+    Closed,
+    /// Listening for connections.
+    Listen,
+    /// Sent a connection request, waiting for a matching request.
+    SynSent,
+    /// Received a connection request, waiting for the acknowledgement.
+    SynReceived,
+    /// Connection is open.
+    Established,
+    /// Received a connection termination request, waiting for the local user
+    /// to close the connection.
+    CloseWait,
+    /// Sent a connection termination request, waiting for the
+    /// acknowledgement.
+    FinWait1,
+    /// Both sides sent a connection termination request at the same time.
+    Closing,
+    /// Waiting for the acknowledgement of the termination request sent after
+    /// receiving one.
+    LastAck,
+    /// Waiting for a connection termination request from the peer.
+    FinWait2,
+    /// Waiting to make sure the peer received the acknowledgement of its
+    /// termination request.
+    TimeWait,
+}
+
+impl TcpState {
+    /// Convert the state as used by the BSDs, `TCPS_*` in `netinet/tcp_fsm.h`.
+    #[cfg(not(any(target_os = "android", target_os = "linux", windows)))]
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn from_bsd(state: u32) -> TcpState {
+        match state {
+            1 => TcpState::Listen,
+            2 => TcpState::SynSent,
+            3 => TcpState::SynReceived,
+            4 => TcpState::Established,
+            5 => TcpState::CloseWait,
+            6 => TcpState::FinWait1,
+            7 => TcpState::Closing,
+            8 => TcpState::LastAck,
+            9 => TcpState::FinWait2,
+            10 => TcpState::TimeWait,
+            _ => TcpState::Closed,
+        }
+    }
+
+    /// Convert the state as used by Windows, `TCPSTATE_*` in `mstcpip.h`.
+    #[cfg(windows)]
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn from_windows(
+        state: windows_sys::Win32::Networking::WinSock::TCPSTATE,
+    ) -> TcpState {
+        use windows_sys::Win32::Networking::WinSock::{
+            TCPSTATE_CLOSE_WAIT, TCPSTATE_CLOSING, TCPSTATE_ESTABLISHED, TCPSTATE_FIN_WAIT_1,
+            TCPSTATE_FIN_WAIT_2, TCPSTATE_LAST_ACK, TCPSTATE_LISTEN, TCPSTATE_SYN_RCVD,
+            TCPSTATE_SYN_SENT, TCPSTATE_TIME_WAIT,
+        };
+
+        match state {
+            TCPSTATE_LISTEN => TcpState::Listen,
+            TCPSTATE_SYN_SENT => TcpState::SynSent,
+            TCPSTATE_SYN_RCVD => TcpState::SynReceived,
+            TCPSTATE_ESTABLISHED => TcpState::Established,
+            TCPSTATE_FIN_WAIT_1 => TcpState::FinWait1,
+            TCPSTATE_FIN_WAIT_2 => TcpState::FinWait2,
+            TCPSTATE_CLOSE_WAIT => TcpState::CloseWait,
+            TCPSTATE_CLOSING => TcpState::Closing,
+            TCPSTATE_LAST_ACK => TcpState::LastAck,
+            TCPSTATE_TIME_WAIT => TcpState::TimeWait,
+            _ => TcpState::Closed,
+        }
+    }
+
+    /// Convert the state as used by Linux, `TCP_*` in `netinet/tcp.h`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn from_linux(state: u8) -> TcpState {
+        match state {
+            1 => TcpState::Established,
+            2 => TcpState::SynSent,
+            // Including `TCP_NEW_SYN_RECV`.
+            3 | 12 => TcpState::SynReceived,
+            4 => TcpState::FinWait1,
+            5 => TcpState::FinWait2,
+            6 => TcpState::TimeWait,
+            8 => TcpState::CloseWait,
+            9 => TcpState::LastAck,
+            10 => TcpState::Listen,
+            11 => TcpState::Closing,
+            _ => TcpState::Closed,
+        }
+    }
+}
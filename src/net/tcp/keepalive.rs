@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// Configuration of TCP keepalive probes, used in
+/// [`TcpStream::set_keepalive_params`].
+///
+/// Parameters that are not set keep the value currently set on the socket,
+/// which defaults to the OS wide configuration.
+///
+/// [`TcpStream::set_keepalive_params`]: crate::net::TcpStream::set_keepalive_params
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use mio::net::TcpKeepalive;
+///
+/// let keepalive = TcpKeepalive::new()
+///     .with_time(Duration::from_secs(60))
+///     .with_interval(Duration::from_secs(10))
+///     .with_retries(5);
+/// # let _ = keepalive;
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TcpKeepalive {
+    pub(crate) time: Option<Duration>,
+    pub(crate) interval: Option<Duration>,
+    pub(crate) retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Create a new configuration with no parameters set.
+    pub fn new() -> TcpKeepalive {
+        TcpKeepalive::default()
+    }
+
+    /// Set the amount of time the connection must be idle before the first
+    /// keepalive probe is sent (`TCP_KEEPIDLE`, `TCP_KEEPALIVE` on Apple
+    /// platforms).
+    ///
+    /// The time is rounded down to whole seconds.
+    pub fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Set the time between keepalive probes that are not acknowledged
+    /// (`TCP_KEEPINTVL`).
+    ///
+    /// The interval is rounded down to whole seconds.
+    pub fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Set the number of unacknowledged keepalive probes after which the
+    /// connection is considered dead (`TCP_KEEPCNT`).
+    pub fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive {
+            retries: Some(retries),
+            ..self
+        }
+    }
+}
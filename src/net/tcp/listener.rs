@@ -71,10 +71,10 @@ impl TcpListener {
         // which allows “socket hijacking”, so we explicitly don't set it here.
         // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
         #[cfg(not(windows))]
-        set_reuseaddr(&listener.inner, true)?;
+        set_reuseaddr(&*listener.inner, true)?;
 
-        bind(&listener.inner, addr)?;
-        listen(&listener.inner, 1024)?;
+        bind(&*listener.inner, addr)?;
+        listen(&*listener.inner, 1024)?;
         Ok(listener)
     }
 
@@ -99,7 +99,7 @@ impl TcpListener {
     /// If an accepted stream is returned, the remote address of the peer is
     /// returned along with it.
     pub fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
-        self.inner.do_io(|inner| {
+        self.inner.do_io_with(Interest::READABLE, |inner| {
             sys::tcp::accept(inner).map(|(stream, addr)| (TcpStream::from_std(stream), addr))
         })
     }
@@ -126,6 +126,35 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `TCP_FASTOPEN` option on this socket, enabling
+    /// TCP Fast Open with a queue of at most `queue_len` pending Fast Open
+    /// requests. Zero disables Fast Open.
+    ///
+    /// Connections accepted using Fast Open can be read from before the
+    /// handshake completes. Clients can use
+    /// [`TcpStream::connect_with_data`] to send data in the SYN.
+    ///
+    /// # Notes
+    ///
+    /// Server side Fast Open must also be enabled by bit `0x2` of the
+    /// `net.ipv4.tcp_fastopen` sysctl, which is not set by default.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_fastopen(&self, queue_len: u32) -> io::Result<()> {
+        sys::tcp::set_fastopen(&*self.inner, queue_len)
+    }
+
+    /// Gets the value of the `TCP_FASTOPEN` option on this socket.
+    ///
+    /// For more information about this option, see [`set_fastopen`].
+    ///
+    /// [`set_fastopen`]: TcpListener::set_fastopen
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn fastopen(&self) -> io::Result<u32> {
+        sys::tcp::fastopen(&*self.inner)
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -134,6 +163,15 @@ impl TcpListener {
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Returns the last known readiness of the listener, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
 }
 
 impl event::Source for TcpListener {
@@ -158,6 +196,10 @@ impl event::Source for TcpListener {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl fmt::Debug for TcpListener {
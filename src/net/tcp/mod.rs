@@ -1,5 +1,31 @@
+#[cfg(not(target_os = "wasi"))]
+mod connector;
+#[cfg(not(target_os = "wasi"))]
+pub use self::connector::{TcpConnectError, TcpConnector};
+
+cfg_tcp_info! {
+    mod info;
+    pub use self::info::{TcpInfo, TcpState};
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod fastopen;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::fastopen::TcpFastOpen;
+
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+mod keepalive;
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub use self::keepalive::TcpKeepalive;
+
 mod listener;
 pub use self::listener::TcpListener;
 
 mod stream;
 pub use self::stream::TcpStream;
+
+#[cfg(all(unix, feature = "os-poll"))]
+mod socket;
+#[cfg(all(unix, feature = "os-poll"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-poll"))))]
+pub use self::socket::TcpSocket;
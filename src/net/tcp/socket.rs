@@ -0,0 +1,201 @@
+use std::io;
+use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+
+use crate::net::{TcpListener, TcpStream};
+use crate::sys;
+use crate::sys::tcp::{bind, connect, listen, new_for_addr, set_reuseaddr};
+
+/// A non-blocking TCP socket used to configure a stream or listener before it
+/// is connected or starts listening.
+///
+/// [`TcpStream::connect`] and [`TcpListener::bind`] use fixed options, for
+/// example the listener always has `SO_REUSEADDR` set and uses a backlog of
+/// 1024. `TcpSocket` allows the socket options to be set before calling
+/// [`connect`] or [`listen`], which return a [`TcpStream`] or
+/// [`TcpListener`] respectively.
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// let addr = "127.0.0.1:0".parse()?;
+///
+/// let socket = TcpSocket::new_for_addr(addr)?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_recv_buffer_size(64 * 1024)?;
+/// socket.bind(addr)?;
+///
+/// let listener = socket.listen(128)?;
+/// # drop(listener);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    socket: OwnedFd,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0))
+    }
+
+    /// Create a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0))
+    }
+
+    /// Create a new TCP socket for the same IP version as `addr`.
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+        // SAFETY: `new_for_addr` ensures the fd is valid.
+        new_for_addr(addr).map(|socket| unsafe { TcpSocket::from_raw_fd(socket) })
+    }
+
+    /// Bind the socket to `addr`.
+    ///
+    /// This can be used to select the local address (or port) used by
+    /// [`connect`], and is required before calling [`listen`].
+    ///
+    /// [`connect`]: TcpSocket::connect
+    /// [`listen`]: TcpSocket::listen
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        bind(&self.socket, addr)
+    }
+
+    /// Issue a non-blocking connect to `addr`, converting the socket into a
+    /// [`TcpStream`].
+    ///
+    /// The returned stream may not be connected yet, see
+    /// [`TcpStream::connect`] for how to determine when it is.
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        connect(&self.socket, addr)?;
+        // SAFETY: `into_raw_fd` ensures the fd is valid.
+        let stream = unsafe { net::TcpStream::from_raw_fd(self.into_raw_fd()) };
+        Ok(TcpStream::from_std(stream))
+    }
+
+    /// Start listening for incoming connections, converting the socket into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of pending connections, the OS may
+    /// limit it further (e.g. `net.core.somaxconn` on Linux).
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        listen(&self.socket, backlog)?;
+        // SAFETY: `into_raw_fd` ensures the fd is valid.
+        let listener = unsafe { net::TcpListener::from_raw_fd(self.into_raw_fd()) };
+        Ok(TcpListener::from_std(listener))
+    }
+
+    /// Sets the value of `SO_REUSEADDR` on this socket.
+    ///
+    /// This allows binding to an address that is still in the `TIME_WAIT`
+    /// state, e.g. when restarting a server.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        set_reuseaddr(&self.socket, reuseaddr)
+    }
+
+    /// Gets the value of `SO_REUSEADDR` on this socket.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        sys::tcp::reuseaddr(&self.socket)
+    }
+
+    /// Sets the value of `SO_REUSEPORT` on this socket.
+    ///
+    /// This allows multiple sockets to bind to the same address and port, the
+    /// exact semantics differ per OS. On Linux incoming connections are
+    /// distributed over the listeners bound to the address.
+    #[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(unix, not(any(target_os = "illumos", target_os = "solaris")))))
+    )]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        sys::tcp::set_reuseport(&self.socket, reuseport)
+    }
+
+    /// Gets the value of `SO_REUSEPORT` on this socket.
+    #[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(unix, not(any(target_os = "illumos", target_os = "solaris")))))
+    )]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        sys::tcp::reuseport(&self.socket)
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// Note that the OS may adjust the size, e.g. Linux doubles it to allow
+    /// space for bookkeeping.
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(&self.socket, size)
+    }
+
+    /// Gets the value of `SO_SNDBUF` on this socket.
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::send_buffer_size(&self.socket)
+    }
+
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// Note that the OS may adjust the size, e.g. Linux doubles it to allow
+    /// space for bookkeeping.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(&self.socket, size)
+    }
+
+    /// Gets the value of `SO_RCVBUF` on this socket.
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::recv_buffer_size(&self.socket)
+    }
+
+    /// Sets the value of `IPV6_V6ONLY` on this socket.
+    ///
+    /// If set an IPv6 socket only communicates using IPv6, otherwise it can
+    /// also be used for IPv4 using IPv4-mapped addresses. Only valid for IPv6
+    /// sockets.
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        sys::tcp::set_only_v6(&self.socket, only_v6)
+    }
+
+    /// Gets the value of `IPV6_V6ONLY` on this socket.
+    pub fn only_v6(&self) -> io::Result<bool> {
+        sys::tcp::only_v6(&self.socket)
+    }
+}
+
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.socket.into_raw_fd()
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+impl FromRawFd for TcpSocket {
+    /// Converts a `RawFd` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket {
+            socket: OwnedFd::from_raw_fd(fd),
+        }
+    }
+}
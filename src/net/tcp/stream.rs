@@ -1,4 +1,7 @@
 use std::fmt;
+cfg_sendfile! {
+    use std::fs::File;
+}
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::{self, Shutdown, SocketAddr};
 #[cfg(unix)]
@@ -7,8 +10,14 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::wasi::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+use std::time::Duration;
 
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::TcpFastOpen;
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+use crate::net::TcpKeepalive;
 #[cfg(not(target_os = "wasi"))]
 use crate::sys::tcp::{connect, new_for_addr};
 use crate::{event, Interest, Registry, Token};
@@ -87,10 +96,42 @@ impl TcpStream {
         let stream = unsafe { TcpStream::from_raw_fd(socket) };
         #[cfg(windows)]
         let stream = unsafe { TcpStream::from_raw_socket(socket as _) };
-        connect(&stream.inner, addr)?;
+        connect(&*stream.inner, addr)?;
         Ok(stream)
     }
 
+    /// Create a new TCP stream and issue a non-blocking connect to the
+    /// specified address using TCP Fast Open, sending `data` along with the
+    /// SYN.
+    ///
+    /// Returns the stream and how the connection was started, see
+    /// [`TcpFastOpen`]. The bytes of `data` that were sent (see
+    /// [`TcpFastOpen::sent`]) are delivered to the peer once the connection is
+    /// established, the remaining bytes must be written as usual. If the peer
+    /// rejects the data in the SYN the kernel retransmits it after the
+    /// handshake.
+    ///
+    /// The connection is completed the same way as with [`connect`].
+    ///
+    /// [`connect`]: TcpStream::connect
+    ///
+    /// # Notes
+    ///
+    /// Client side Fast Open is enabled by bit `0x1` of the
+    /// `net.ipv4.tcp_fastopen` sysctl, which is set by default. Data sent in
+    /// the SYN may be delivered more than once, it should be idempotent.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn connect_with_data(
+        addr: SocketAddr,
+        data: &[u8],
+    ) -> io::Result<(TcpStream, TcpFastOpen)> {
+        let socket = new_for_addr(addr)?;
+        let stream = unsafe { TcpStream::from_raw_fd(socket) };
+        let fastopen = crate::sys::tcp::connect_with_data(&*stream.inner, addr, data)?;
+        Ok((stream, fastopen))
+    }
+
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
     ///
     /// This function is intended to be used to wrap a TCP stream from the
@@ -198,6 +239,266 @@ impl TcpStream {
         self.inner.take_error()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If enabled keepalive probes are sent on an idle connection, closing the
+    /// connection if the peer stops responding. See [`set_keepalive_params`]
+    /// to configure when the probes are sent.
+    ///
+    /// [`set_keepalive_params`]: TcpStream::set_keepalive_params
+    ///
+    /// # Notes
+    ///
+    /// On Windows make sure the stream is connected before calling this method,
+    /// by receiving an (writable) event. Trying to set `keepalive` on an
+    /// unconnected `TcpStream` is unspecified behavior.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        crate::sys::tcp::set_keepalive(&*self.inner, keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: TcpStream::set_keepalive
+    #[cfg(not(target_os = "wasi"))]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        crate::sys::tcp::keepalive(&*self.inner)
+    }
+
+    /// Enables keepalive (`SO_KEEPALIVE`) and sets the parameters in
+    /// `keepalive` on this socket.
+    ///
+    /// # Notes
+    ///
+    /// On Windows this requires Windows 10 version 1709 or later. Make sure
+    /// the stream is connected before calling this method, by receiving an
+    /// (writable) event.
+    #[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(target_os = "openbsd", target_os = "wasi")))))]
+    pub fn set_keepalive_params(&self, keepalive: TcpKeepalive) -> io::Result<()> {
+        crate::sys::tcp::set_keepalive_params(&*self.inner, keepalive)
+    }
+
+    /// Gets the amount of time the connection must be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// For more information about this option, see [`TcpKeepalive::with_time`].
+    #[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(target_os = "openbsd", target_os = "wasi")))))]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        crate::sys::tcp::keepalive_time(&*self.inner)
+    }
+
+    /// Gets the time between unacknowledged keepalive probes.
+    ///
+    /// For more information about this option, see
+    /// [`TcpKeepalive::with_interval`].
+    #[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(target_os = "openbsd", target_os = "wasi")))))]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        crate::sys::tcp::keepalive_interval(&*self.inner)
+    }
+
+    /// Gets the number of unacknowledged keepalive probes after which the
+    /// connection is considered dead.
+    ///
+    /// For more information about this option, see
+    /// [`TcpKeepalive::with_retries`].
+    #[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(target_os = "openbsd", target_os = "wasi")))))]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        crate::sys::tcp::keepalive_retries(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time transmitted data may remain
+    /// unacknowledged before the connection is closed. If keepalive is
+    /// enabled it also overrides the number of keepalive probes sent. `None`
+    /// uses the system default. The timeout is rounded down to whole
+    /// milliseconds.
+    ///
+    /// This is not available on Windows. Its closest equivalent, `TCP_MAXRT`,
+    /// also limits the retransmissions of the connection handshake and
+    /// doesn't interact with keepalive, so it's not exposed under this name.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        crate::sys::tcp::set_user_timeout(&*self.inner, timeout)
+    }
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_user_timeout`].
+    ///
+    /// [`set_user_timeout`]: TcpStream::set_user_timeout
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        crate::sys::tcp::user_timeout(&*self.inner)
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket, which must
+    /// be enabled to use [`send_zerocopy`].
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        crate::sys::zerocopy::set_zerocopy(&*self.inner, zerocopy)
+    }
+
+    /// Gets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// For more information about this option, see [`set_zerocopy`].
+    ///
+    /// [`set_zerocopy`]: TcpStream::set_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        crate::sys::zerocopy::zerocopy(&*self.inner)
+    }
+
+    /// Sends data on the socket without copying it, using `MSG_ZEROCOPY`.
+    ///
+    /// Like [`write`] this may send fewer bytes than requested. Each successful
+    /// call is assigned the next ID (starting at zero), the kernel keeps using
+    /// `buf` until a [`ZerocopyCompletion`] containing that ID is received
+    /// using [`recv_zerocopy_completion`], so `buf` must not be modified or
+    /// freed before then. A failed call doesn't use an ID.
+    ///
+    /// If too many sends are pending this returns an `ENOBUFS` error,
+    /// completions must be received before sending again.
+    ///
+    /// [`write`]: Write::write
+    /// [`ZerocopyCompletion`]: crate::net::ZerocopyCompletion
+    /// [`recv_zerocopy_completion`]: TcpStream::recv_zerocopy_completion
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.do_io_with(Interest::WRITABLE, |inner| {
+            crate::sys::zerocopy::send(inner, buf, None)
+        })
+    }
+
+    /// Receives a single completion notification of sends using
+    /// [`send_zerocopy`] from the socket's error queue (`MSG_ERRQUEUE`).
+    ///
+    /// Pending completions are signalled by an [error event], they should be
+    /// received until this returns a [`WouldBlock`] error. Doing so doesn't
+    /// change the socket's (read or write) readiness. Errors from the error
+    /// queue that are not completions are returned as errors.
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    /// [error event]: crate::event::Event::is_error
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_zerocopy_completion(&self) -> io::Result<crate::net::ZerocopyCompletion> {
+        self.inner.do_io(crate::sys::zerocopy::recv_completion)
+    }
+
+    cfg_tcp_info! {
+        /// Returns information about the connection, such as the round trip time
+        /// and congestion window.
+        ///
+        /// See [`TcpInfo`] for the OS specific details.
+        ///
+        /// [`TcpInfo`]: crate::net::TcpInfo
+        pub fn tcp_info(&self) -> io::Result<crate::net::TcpInfo> {
+            crate::sys::tcp::tcp_info(&*self.inner)
+        }
+    }
+
+    /// Sets the congestion control algorithm used for this socket, the value
+    /// of the `TCP_CONGESTION` option, e.g. `"cubic"` or `"bbr"`.
+    ///
+    /// The algorithm must be available, e.g. on Linux listed in
+    /// `net.ipv4.tcp_available_congestion_control`. Unprivileged processes
+    /// can only use the algorithms listed in
+    /// `net.ipv4.tcp_allowed_congestion_control`.
+    #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux")))
+    )]
+    pub fn set_congestion(&self, name: &str) -> io::Result<()> {
+        crate::sys::tcp::set_congestion(&*self.inner, name)
+    }
+
+    /// Gets the value of the `TCP_CONGESTION` option on this socket.
+    ///
+    /// For more information about this option, see [`set_congestion`].
+    ///
+    /// [`set_congestion`]: TcpStream::set_congestion
+    #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux")))
+    )]
+    pub fn congestion(&self) -> io::Result<String> {
+        crate::sys::tcp::congestion(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_MAXSEG` option on this socket, limiting the
+    /// maximum segment size used for sending.
+    ///
+    /// The OS may adjust the value, e.g. to stay within its own limits, and
+    /// on some OSs it only has an effect if set before connecting.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_max_segment_size(&self, mss: u32) -> io::Result<()> {
+        crate::sys::tcp::set_max_segment_size(&*self.inner, mss)
+    }
+
+    /// Gets the value of the `TCP_MAXSEG` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_max_segment_size`].
+    ///
+    /// [`set_max_segment_size`]: TcpStream::set_max_segment_size
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn max_segment_size(&self) -> io::Result<u32> {
+        crate::sys::tcp::max_segment_size(&*self.inner)
+    }
+
+    /// Returns the CPU the packets of this stream are processed on, the value
+    /// of the `SO_INCOMING_CPU` option.
+    ///
+    /// This can be used to hand the stream to a thread running on the same
+    /// CPU, see [`ReuseportGroup::steer_by_cpu`].
+    ///
+    /// [`ReuseportGroup::steer_by_cpu`]: crate::net::ReuseportGroup::steer_by_cpu
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn incoming_cpu(&self) -> io::Result<u32> {
+        crate::sys::tcp::incoming_cpu(&*self.inner)
+    }
+
+    cfg_sendfile! {
+        /// Sends up to `len` bytes of `file`, starting at `offset`, to the
+        /// stream using `sendfile(2)`, without copying the data through user
+        /// space. Returns the number of bytes sent, zero if `offset` is at or past
+        /// the end of the file (or `len` is zero).
+        ///
+        /// Like [`write`] this may send fewer bytes than requested, call it again
+        /// with an updated `offset` and `len` to send the remainder. If the send
+        /// buffer is full a [`WouldBlock`] error is returned, the stream becomes
+        /// writable again once there is room. The position of `file` is not used
+        /// or changed.
+        ///
+        /// [`write`]: Write::write
+        /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+        pub fn sendfile(&self, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+            self.inner.do_io_with(Interest::WRITABLE, |inner| {
+                crate::sys::tcp::sendfile(inner, file, offset, len)
+            })
+        }
+    }
+
     /// Receives data on the socket from the remote address to which it is
     /// connected, without removing that data from the queue. On success,
     /// returns the number of bytes peeked.
@@ -205,7 +506,23 @@ impl TcpStream {
     /// Successive calls return the same data. This is accomplished by passing
     /// `MSG_PEEK` as a flag to the underlying recv system call.
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.peek(buf)
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.peek(buf))
+    }
+
+    /// Returns the last known readiness of the stream, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// # Notes
+    ///
+    /// Receiving zero-copy completions (Linux only) doesn't update the readiness,
+    /// the error queue being empty says nothing about the readiness of the
+    /// stream.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
     }
 
     /// Execute an I/O operation ensuring that the socket receives more events
@@ -269,49 +586,59 @@ impl TcpStream {
 
 impl Read for TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read_vectored(bufs))
     }
 }
 
 impl<'a> Read for &'a TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read_vectored(bufs))
     }
 }
 
 impl Write for TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut inner| inner.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.flush())
     }
 }
 
 impl<'a> Write for &'a TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut inner| inner.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.flush())
     }
 }
 
@@ -337,6 +664,10 @@ impl event::Source for TcpStream {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl fmt::Debug for TcpStream {
@@ -209,7 +209,8 @@ impl UdpSocket {
     /// # }
     /// ```
     pub fn send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.send_to(buf, target))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |inner| inner.send_to(buf, target))
     }
 
     /// Receives data from the socket. On success, returns the number of bytes
@@ -244,7 +245,8 @@ impl UdpSocket {
     /// # }
     /// ```
     pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
-        self.inner.do_io(|inner| inner.recv_from(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.recv_from(buf))
     }
 
     /// Receives data from the socket, without removing it from the input queue.
@@ -280,13 +282,15 @@ impl UdpSocket {
     /// # }
     /// ```
     pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
-        self.inner.do_io(|inner| inner.peek_from(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.peek_from(buf))
     }
 
     /// Sends data on the socket to the address previously bound via connect(). On success,
     /// returns the number of bytes written.
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.send(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |inner| inner.send(buf))
     }
 
     /// Receives data from the socket previously bound with connect(). On success, returns
@@ -300,7 +304,8 @@ impl UdpSocket {
     /// Make sure to always use a sufficiently large buffer to hold the
     /// maximum UDP packet size, which can be up to 65536 bytes in size.
     pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.recv(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.recv(buf))
     }
 
     /// Receives data from the socket, without removing it from the input queue.
@@ -314,7 +319,8 @@ impl UdpSocket {
     /// Make sure to always use a sufficiently large buffer to hold the
     /// maximum UDP packet size, which can be up to 65536 bytes in size.
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.peek(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.peek(buf))
     }
 
     /// Connects the UDP socket setting the default destination for `send()`
@@ -553,6 +559,96 @@ impl UdpSocket {
         self.inner.take_error()
     }
 
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket, which must
+    /// be enabled to use [`send_zerocopy`].
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        crate::sys::zerocopy::set_zerocopy(&*self.inner, zerocopy)
+    }
+
+    /// Gets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// For more information about this option, see [`set_zerocopy`].
+    ///
+    /// [`set_zerocopy`]: UdpSocket::set_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        crate::sys::zerocopy::zerocopy(&*self.inner)
+    }
+
+    /// Sends data on the socket without copying it, using `MSG_ZEROCOPY`.
+    ///
+    /// The socket must be connected, see [`send_to_zerocopy`] otherwise. Each
+    /// successful call is assigned the next ID (starting at zero), the kernel
+    /// keeps using `buf` until a [`ZerocopyCompletion`] containing that ID is
+    /// received using [`recv_zerocopy_completion`], so `buf` must not be
+    /// modified or freed before then. A failed call doesn't use an ID.
+    ///
+    /// If too many sends are pending this returns an `ENOBUFS` error,
+    /// completions must be received before sending again.
+    ///
+    /// [`send_to_zerocopy`]: UdpSocket::send_to_zerocopy
+    /// [`ZerocopyCompletion`]: crate::net::ZerocopyCompletion
+    /// [`recv_zerocopy_completion`]: UdpSocket::recv_zerocopy_completion
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.do_io_with(Interest::WRITABLE, |inner| {
+            crate::sys::zerocopy::send(inner, buf, None)
+        })
+    }
+
+    /// Sends data on the socket to the given address without copying it,
+    /// using `MSG_ZEROCOPY`.
+    ///
+    /// This uses the same IDs as [`send_zerocopy`], see it for more
+    /// information.
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_to_zerocopy(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        self.inner.do_io_with(Interest::WRITABLE, |inner| {
+            crate::sys::zerocopy::send(inner, buf, Some(target))
+        })
+    }
+
+    /// Receives a single completion notification of sends using
+    /// [`send_zerocopy`] from the socket's error queue (`MSG_ERRQUEUE`).
+    ///
+    /// Pending completions are signalled by an [error event], they should be
+    /// received until this returns a [`WouldBlock`] error. Doing so doesn't
+    /// change the socket's (read or write) readiness. Errors from the error
+    /// queue that are not completions are returned as errors.
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    /// [error event]: crate::event::Event::is_error
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_zerocopy_completion(&self) -> io::Result<crate::net::ZerocopyCompletion> {
+        self.inner.do_io(crate::sys::zerocopy::recv_completion)
+    }
+
+    /// Returns the last known readiness of the socket, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// # Notes
+    ///
+    /// Receiving zero-copy completions (Linux only) doesn't update the readiness,
+    /// the error queue being empty says nothing about the readiness of the
+    /// socket.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -634,6 +730,10 @@ impl event::Source for UdpSocket {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl fmt::Debug for UdpSocket {
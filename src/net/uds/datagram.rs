@@ -70,22 +70,25 @@ impl UnixDatagram {
     /// On success, returns the number of bytes read and the address from
     /// whence the data came.
     pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, sys::SocketAddr)> {
-        self.inner
-            .do_io(|inner| sys::uds::datagram::recv_from(inner, buf))
+        self.inner.do_io_with(Interest::READABLE, |inner| {
+            sys::uds::datagram::recv_from(inner, buf)
+        })
     }
 
     /// Receives data from the socket.
     ///
     /// On success, returns the number of bytes read.
     pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.recv(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |inner| inner.recv(buf))
     }
 
     /// Sends data on the socket to the specified address.
     ///
     /// On success, returns the number of bytes written.
     pub fn send_to<P: AsRef<Path>>(&self, buf: &[u8], path: P) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.send_to(buf, path))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |inner| inner.send_to(buf, path))
     }
 
     /// Sends data on the socket to the socket's peer.
@@ -95,7 +98,8 @@ impl UnixDatagram {
     ///
     /// On success, returns the number of bytes written.
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| inner.send(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |inner| inner.send(buf))
     }
 
     /// Returns the value of the `SO_ERROR` option.
@@ -112,6 +116,15 @@ impl UnixDatagram {
         self.inner.shutdown(how)
     }
 
+    /// Returns the last known readiness of the socket, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -203,6 +216,10 @@ impl event::Source for UnixDatagram {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl fmt::Debug for UnixDatagram {
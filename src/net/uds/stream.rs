@@ -2,6 +2,9 @@ use crate::io_source::IoSource;
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
+cfg_sendfile! {
+    use std::fs::File;
+}
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::Shutdown;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
@@ -64,6 +67,27 @@ impl UnixStream {
         self.inner.take_error()
     }
 
+    cfg_sendfile! {
+        /// Sends up to `len` bytes of `file`, starting at `offset`, to the
+        /// stream using `sendfile(2)`, without copying the data through user
+        /// space. Returns the number of bytes sent, zero if `offset` is at or past
+        /// the end of the file (or `len` is zero).
+        ///
+        /// Like [`write`] this may send fewer bytes than requested, call it again
+        /// with an updated `offset` and `len` to send the remainder. If the send
+        /// buffer is full a [`WouldBlock`] error is returned, the stream becomes
+        /// writable again once there is room. The position of `file` is not used
+        /// or changed.
+        ///
+        /// [`write`]: Write::write
+        /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+        pub fn sendfile(&self, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+            self.inner.do_io_with(Interest::WRITABLE, |inner| {
+                sys::uds::stream::sendfile(inner, file, offset, len)
+            })
+        }
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -73,6 +97,15 @@ impl UnixStream {
         self.inner.shutdown(how)
     }
 
+    /// Returns the last known readiness of the stream, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -144,49 +177,59 @@ impl UnixStream {
 
 impl Read for UnixStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read_vectored(bufs))
     }
 }
 
 impl<'a> Read for &'a UnixStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut inner| inner.read_vectored(bufs))
     }
 }
 
 impl Write for UnixStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut inner| inner.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.flush())
     }
 }
 
 impl<'a> Write for &'a UnixStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut inner| inner.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut inner| inner.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut inner| inner.flush())
     }
 }
 
@@ -212,6 +255,10 @@ impl event::Source for UnixStream {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl fmt::Debug for UnixStream {
@@ -0,0 +1,47 @@
+/// Completion notification of sends using `MSG_ZEROCOPY`.
+///
+/// Each successful zero-copy send on a socket is assigned an ID, starting at
+/// zero and incremented by one for each send. A completion reports a range of
+/// IDs for which the kernel no longer uses the buffers, so the buffers passed
+/// to those sends can be reused or freed.
+///
+/// The IDs wrap around after `u32::MAX`, so a range can start at a larger ID
+/// than it ends at, which [`contains`] takes into account.
+///
+/// [`contains`]: ZerocopyCompletion::contains
+///
+/// Completions are retrieved using [`TcpStream::recv_zerocopy_completion`] or
+/// [`UdpSocket::recv_zerocopy_completion`].
+///
+/// [`TcpStream::recv_zerocopy_completion`]: crate::net::TcpStream::recv_zerocopy_completion
+/// [`UdpSocket::recv_zerocopy_completion`]: crate::net::UdpSocket::recv_zerocopy_completion
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ZerocopyCompletion {
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+    pub(crate) copied: bool,
+}
+
+impl ZerocopyCompletion {
+    /// Returns the ID of the first completed send.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the ID of the last completed send (inclusive).
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns `true` if the send with `id` is completed by this completion.
+    pub fn contains(&self, id: u32) -> bool {
+        id.wrapping_sub(self.start) <= self.end.wrapping_sub(self.start)
+    }
+
+    /// Returns `true` if the kernel fell back to copying the data for (some
+    /// of) the sends, e.g. when sending over the loopback interface. If this
+    /// is common zero-copy sends are less efficient than regular sends.
+    pub fn copied(&self) -> bool {
+        self.copied
+    }
+}
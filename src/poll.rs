@@ -1,8 +1,12 @@
+#[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+use crate::io_source::ReadinessTable;
 use crate::{event, sys, Events, Interest, Token};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
-use std::time::Duration;
-use std::{fmt, io};
+#[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{cmp, fmt, io};
 
 /// Polls for readiness events on all registered values.
 ///
@@ -94,6 +98,24 @@ use std::{fmt, io};
 ///
 /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
 ///
+/// ### Readiness cache
+///
+/// To help with the above Mio's own I/O types, such as [`TcpStream`], keep
+/// track of their last known readiness, returned by their `readiness` method.
+/// Initially an I/O source is assumed to be ready for all operations. Once an
+/// operation, such as `read`, returns a [`WouldBlock`] error the readiness for
+/// that operation is cleared, until `Poll::poll` returns an event for the I/O
+/// source. This can be used to skip operations that are known to return
+/// `WouldBlock`.
+///
+/// Events are matched to I/O sources using their token, so if multiple I/O
+/// sources are registered using the same token an event for one marks all of
+/// them as ready. Errors and closed halves are considered ready, as the
+/// operation won't block. Operations performed using `try_io` don't update
+/// the readiness.
+///
+/// [`TcpStream`]: ./net/struct.TcpStream.html
+///
 /// ### Readiness operations
 ///
 /// The only readiness operations that are guaranteed to be present on all
@@ -247,11 +269,15 @@ use std::{fmt, io};
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    /// Busy-poll budget, see `Poll::set_busy_poll`.
+    busy_poll: Option<Duration>,
 }
 
 /// Registers I/O resources.
 pub struct Registry {
     selector: sys::Selector,
+    #[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+    readiness: Arc<ReadinessTable>,
 }
 
 impl Poll {
@@ -298,7 +324,8 @@ impl Poll {
         /// ```
         pub fn new() -> io::Result<Poll> {
             sys::Selector::new().map(|selector| Poll {
-                registry: Registry { selector },
+                registry: Registry::new(selector),
+                busy_poll: None,
             })
         }
     }
@@ -352,6 +379,9 @@ impl Poll {
     /// triggering this will return `Ok(())`. However we're not guaranteeing
     /// this behaviour as this depends on the OS.
     ///
+    /// If busy-polling is enabled this will first spin for events before
+    /// blocking, see [`Poll::set_busy_poll`].
+    ///
     /// # Examples
     ///
     /// A basic example -- establishing a `TcpStream` connection.
@@ -407,8 +437,104 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.select(events, timeout)?;
+        self.registry.update_readiness(events);
+        Ok(())
+    }
+
+    /// Wait for readiness events, see [`Poll::poll`].
+    fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        let timeout = match self.busy_poll {
+            Some(budget) => {
+                let start = Instant::now();
+                let spin = timeout.map_or(budget, |to| cmp::min(to, budget));
+                loop {
+                    self.registry
+                        .selector
+                        .select(events.sys(), Some(Duration::from_millis(0)))?;
+                    if !events.is_empty() {
+                        return Ok(());
+                    } else if start.elapsed() >= spin {
+                        break;
+                    }
+                }
+                // Spent the entire budget without receiving any events, so we
+                // go to sleep for the remainder of the timeout.
+                timeout.map(|to| to.checked_sub(start.elapsed()).unwrap_or_default())
+            }
+            None => timeout,
+        };
         self.registry.selector.select(events.sys(), timeout)
     }
+
+    /// Enable, or disable, busy-polling.
+    ///
+    /// When busy-polling is enabled [`Poll::poll`] will first spin, polling
+    /// for events using a zero timeout, for at most `budget` before going to
+    /// sleep for the remainder of the timeout. This avoids the cost of putting
+    /// the thread to sleep and waking it up again at the expense of CPU usage,
+    /// which can be worth it for low-latency workloads. Passing `None` disables
+    /// busy-polling.
+    ///
+    /// On Linux this also configures the epoll instance to busy-poll the
+    /// network device queues of the registered sockets (`EPIOCSPARAMS`, Linux
+    /// v6.9+) and, if that succeeds, sets `SO_BUSY_POLL` on sockets registered
+    /// or reregistered after this call. This is done on a best effort basis,
+    /// as it depends on the kernel version and permissions of the process, use
+    /// [`Poll::is_kernel_busy_poll`] to determine if it's in effect. Note that
+    /// sockets already registered with the [`Registry`] don't have
+    /// `SO_BUSY_POLL` changed until they're reregistered, this applies to
+    /// disabling busy-polling as well (which resets it to zero).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// // Spin for at most 50 microseconds before going to sleep.
+    /// poll.set_busy_poll(Some(Duration::from_micros(50)));
+    /// println!("kernel busy-polling: {}", poll.is_kernel_busy_poll());
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_busy_poll(&mut self, budget: Option<Duration>) {
+        self.busy_poll = budget.filter(|budget| *budget != Duration::from_millis(0));
+
+        #[cfg(all(target_os = "linux", feature = "os-poll"))]
+        {
+            // `EPIOCSPARAMS` doesn't accept values larger than `i32::MAX`.
+            let usecs = self.busy_poll.map_or(0, |budget| {
+                cmp::min(budget.as_micros(), i32::MAX as u128) as u32
+            });
+            if let Err(err) = self.registry.selector.set_busy_poll(usecs) {
+                trace!("unable to configure kernel busy-polling: {}", err);
+            }
+        }
+    }
+
+    /// Returns true if the kernel busy-polls on behalf of this `Poll`
+    /// instance.
+    ///
+    /// This is only ever true on Linux, see [`Poll::set_busy_poll`].
+    pub fn is_kernel_busy_poll(&self) -> bool {
+        #[cfg(all(target_os = "linux", feature = "os-poll"))]
+        {
+            self.registry.selector.is_busy_poll()
+        }
+        #[cfg(not(all(target_os = "linux", feature = "os-poll")))]
+        {
+            false
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -418,13 +544,223 @@ impl AsRawFd for Poll {
     }
 }
 
+cfg_os_poll! {
+    #[cfg(unix)]
+    use std::os::unix::io::{FromRawFd, IntoRawFd, OwnedFd};
+
+    #[cfg(unix)]
+    impl FromRawFd for Poll {
+        /// Creates a `Poll` from an existing selector, i.e. an epoll or kqueue
+        /// file descriptor, taking ownership of it.
+        ///
+        /// # Notes
+        ///
+        /// All sources registered with the selector, by Mio or otherwise,
+        /// remain registered. Events for them will be returned by
+        /// [`Poll::poll`] with the user data of the registration interpreted as
+        /// [`Token`].
+        ///
+        /// Mio doesn't keep any state about the selector besides the file
+        /// descriptor, which means that:
+        ///  * A [`Waker`] created for a previous owner of the selector keeps
+        ///    working for as long as it's alive, but Mio doesn't know about it.
+        ///    The caller is responsible for ensuring only a single `Waker` is
+        ///    used per `Poll` instance.
+        ///  * Busy polling (see [`Poll::set_busy_poll`]) is disabled and
+        ///    registrations don't use the one-shot mode of [`SharedPoll`].
+        ///  * In debug builds Mio's own source types, such as [`TcpStream`],
+        ///    remember the `Poll` they're registered with. Those sources must
+        ///    be deregistered before the selector is transferred, sources
+        ///    registered using [`SourceFd`] don't have this restriction.
+        ///
+        /// # Safety
+        ///
+        /// `fd` must be an open epoll (Android, illumos and Linux) or kqueue
+        /// (BSDs and Apple platforms) file descriptor that isn't owned by
+        /// anything else.
+        ///
+        /// [`Waker`]: crate::Waker
+        /// [`TcpStream`]: crate::net::TcpStream
+        /// [`SourceFd`]: crate::unix::SourceFd
+        unsafe fn from_raw_fd(fd: RawFd) -> Poll {
+            Poll {
+                registry: Registry::new(sys::Selector::from_raw_fd(fd)),
+                busy_poll: None,
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    impl IntoRawFd for Poll {
+        /// Returns the selector's file descriptor, without closing it.
+        ///
+        /// All sources registered with the `Poll` remain registered with the
+        /// selector, see [`Poll::from_raw_fd`] for using it again.
+        fn into_raw_fd(self) -> RawFd {
+            self.registry.selector.into_raw_fd()
+        }
+    }
+
+    #[cfg(unix)]
+    impl From<OwnedFd> for Poll {
+        /// Same as [`Poll::from_raw_fd`], see its documentation for the
+        /// requirements on `fd`.
+        fn from(fd: OwnedFd) -> Poll {
+            // Safety: `OwnedFd` ensures we're the only owner of the file
+            // descriptor. If it's not a selector all operations on `Poll`
+            // simply return an error.
+            unsafe { Poll::from_raw_fd(fd.into_raw_fd()) }
+        }
+    }
+}
+
 impl fmt::Debug for Poll {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Poll").finish()
     }
 }
 
+/// Polls for readiness events from multiple threads at once.
+///
+/// `SharedPoll` is similar to [`Poll`], but [`SharedPoll::poll`] takes `&self`
+/// allowing a pool of threads to wait on the same system selector, each using
+/// their own [`Events`]. Combined with an `Arc` this makes it possible to
+/// spread the handling of events over multiple threads.
+///
+/// # One-shot registrations
+///
+/// To ensure each event is handled by exactly one thread all [`event::Source`]s
+/// registered with a `SharedPoll` use *one-shot* semantics: once an event is
+/// returned for a source it is disabled and will not return any more events
+/// until it is [reregistered]. After the thread that received the event is done
+/// processing it, i.e. it has drained the source until it returned a
+/// [`WouldBlock`] error, it must reregister the source to receive further
+/// events.
+///
+/// [`Waker`]s registered with a `SharedPoll` are the exception to this rule,
+/// they don't have to be (and can't be) reregistered.
+///
+/// [reregistered]: Registry::reregister
+/// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+/// [`Waker`]: crate::Waker
+///
+/// # Implementation notes
+///
+/// On platforms using [epoll] one-shot semantics are implemented using
+/// `EPOLLONESHOT`, on platforms using [kqueue] `EV_DISPATCH` is used. Windows
+/// (IOCP) doesn't support polling from multiple threads and thus doesn't
+/// support `SharedPoll`.
+///
+/// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+/// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+/// use std::sync::Arc;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use mio::{Events, SharedPoll};
+///
+/// let poll = Arc::new(SharedPoll::new()?);
+///
+/// // Register `event::Source`s with `poll.registry()`.
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let poll = poll.clone();
+///         thread::spawn(move || {
+///             let mut events = Events::with_capacity(128);
+///             poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+///             for event in events.iter() {
+///                 // Process the event and then reregister the event source
+///                 // to receive more events for it.
+///                 println!("Got an event for {:?}", event.token());
+///             }
+///             Ok::<_, io::Error>(())
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap()?;
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(all(unix, feature = "os-poll"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-poll"))))]
+pub struct SharedPoll {
+    registry: Registry,
+}
+
+#[cfg(all(unix, feature = "os-poll"))]
+impl SharedPoll {
+    /// Return a new `SharedPoll` handle.
+    ///
+    /// See [`Poll::new`] for more details.
+    pub fn new() -> io::Result<SharedPoll> {
+        sys::Selector::new_oneshot().map(|selector| SharedPoll {
+            registry: Registry::new(selector),
+        })
+    }
+
+    /// Create a separate `Registry` which can be used to register
+    /// `event::Source`s.
+    ///
+    /// All sources registered using this `Registry` must be [reregistered]
+    /// after an event is received for them, see the [struct] level
+    /// documentation.
+    ///
+    /// [reregistered]: Registry::reregister
+    /// [struct]: SharedPoll
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Wait for readiness events.
+    ///
+    /// This works the same as [`Poll::poll`], but can be called from multiple
+    /// threads concurrently. Each event will only be returned to a single
+    /// thread, after which the event source must be [reregistered] to receive
+    /// further events.
+    ///
+    /// [reregistered]: Registry::reregister
+    pub fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.registry.selector.select(events.sys(), timeout)?;
+        self.registry.update_readiness(events);
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, feature = "os-poll"))]
+impl AsRawFd for SharedPoll {
+    fn as_raw_fd(&self) -> RawFd {
+        self.registry.as_raw_fd()
+    }
+}
+
+#[cfg(all(unix, feature = "os-poll"))]
+impl fmt::Debug for SharedPoll {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SharedPoll").finish()
+    }
+}
+
 impl Registry {
+    #[cfg(feature = "os-poll")]
+    fn new(selector: sys::Selector) -> Registry {
+        Registry {
+            selector,
+            #[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+            readiness: Arc::new(ReadinessTable::default()),
+        }
+    }
+
     /// Register an [`event::Source`] with the `Poll` instance.
     ///
     /// Once registered, the `Poll` instance will monitor the event source for
@@ -604,6 +940,81 @@ impl Registry {
         source.reregister(self, token, interests)
     }
 
+    /// Pause the delivery of events for a registered [`event::Source`].
+    ///
+    /// Unlike [`deregister`] this keeps the registration with the OS selector,
+    /// avoiding the cost of deregistering and registering the source again,
+    /// and the races that come with reusing tokens. This can be used to apply
+    /// backpressure, e.g. not reading from a connection while a write buffer
+    /// is full. Use [`resume`] to start receiving events again.
+    ///
+    /// `token` must be the same token used when registering the source.
+    ///
+    /// # Notes
+    ///
+    /// This calls [`event::Source::pause`], which isn't supported by all
+    /// sources, e.g. named pipes on Windows and all sources on WASI. Custom
+    /// `event::Source` implementations need to implement it for this to work.
+    ///
+    /// Errors and hang-ups might still be reported while paused, e.g. on Linux
+    /// `epoll(7)` always reports those.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interest, Token};
+    /// use mio::net::TcpStream;
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let listener = net::TcpListener::bind("127.0.0.1:0")?;
+    /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+    ///
+    /// // Stop receiving events for the socket for now.
+    /// poll.registry().pause(&mut socket, Token(0))?;
+    ///
+    /// // And start receiving them again later.
+    /// poll.registry().resume(&mut socket, Token(0), Interest::READABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`deregister`]: Registry::deregister
+    /// [`resume`]: Registry::resume
+    pub fn pause<S>(&self, source: &mut S, token: Token) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!("pausing event source with poller: token={:?}", token);
+        source.pause(self, token)
+    }
+
+    /// Resume the delivery of events for an [`event::Source`] paused using
+    /// [`pause`].
+    ///
+    /// This is the same as calling [`reregister`], the `token` and `interests`
+    /// fully override the previous values.
+    ///
+    /// [`pause`]: Registry::pause
+    /// [`reregister`]: Registry::reregister
+    pub fn resume<S>(&self, source: &mut S, token: Token, interests: Interest) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!(
+            "resuming event source with poller: token={:?}, interests={:?}",
+            token,
+            interests
+        );
+        source.reregister(self, token, interests)
+    }
+
     /// Deregister an [`event::Source`] with the `Poll` instance.
     ///
     /// When an event source is deregistered, the `Poll` instance will no longer
@@ -668,9 +1079,11 @@ impl Registry {
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
     pub fn try_clone(&self) -> io::Result<Registry> {
-        self.selector
-            .try_clone()
-            .map(|selector| Registry { selector })
+        self.selector.try_clone().map(|selector| Registry {
+            selector,
+            #[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+            readiness: self.readiness.clone(),
+        })
     }
 
     /// Internal check to ensure only a single `Waker` is active per [`Poll`]
@@ -683,6 +1096,20 @@ impl Registry {
         );
     }
 
+    /// Sets the readiness of the I/O sources that `events` were returned for.
+    fn update_readiness(&self, events: &Events) {
+        #[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+        self.readiness.update(events);
+        #[cfg(not(any(feature = "net", all(unix, feature = "os-ext"))))]
+        let _ = events;
+    }
+
+    /// Get access to the readiness caches of the registered I/O sources.
+    #[cfg(any(feature = "net", all(unix, feature = "os-ext")))]
+    pub(crate) fn readiness(&self) -> &ReadinessTable {
+        &self.readiness
+    }
+
     /// Get access to the `sys::Selector`.
     #[cfg(any(not(target_os = "wasi"), feature = "net"))]
     pub(crate) fn selector(&self) -> &sys::Selector {
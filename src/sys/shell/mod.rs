@@ -17,6 +17,8 @@ cfg_net! {
     pub(crate) mod udp;
     #[cfg(unix)]
     pub(crate) mod uds;
+    #[cfg(target_os = "linux")]
+    pub(crate) mod zerocopy;
 }
 
 cfg_io_source! {
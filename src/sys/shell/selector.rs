@@ -41,6 +41,10 @@ cfg_any_os_ext! {
         pub fn deregister(&self, _: RawFd) -> io::Result<()> {
             os_required!();
         }
+
+        pub fn pause(&self, _: RawFd, _: Token) -> io::Result<()> {
+            os_required!();
+        }
     }
 }
 
@@ -1,6 +1,9 @@
 use std::io;
 use std::net::{self, SocketAddr};
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::TcpFastOpen;
+
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
     os_required!();
@@ -16,6 +19,25 @@ pub(crate) fn connect(_: &net::TcpStream, _: SocketAddr) -> io::Result<()> {
     os_required!();
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data(
+    _: &net::TcpStream,
+    _: SocketAddr,
+    _: &[u8],
+) -> io::Result<TcpFastOpen> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen(_: &net::TcpListener, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fastopen(_: &net::TcpListener) -> io::Result<u32> {
+    os_required!();
+}
+
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn listen(_: &net::TcpListener, _: u32) -> io::Result<()> {
     os_required!();
@@ -26,6 +48,94 @@ pub(crate) fn set_reuseaddr(_: &net::TcpListener, _: bool) -> io::Result<()> {
     os_required!();
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn incoming_cpu(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_keepalive(_: &net::TcpStream, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn keepalive(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub(crate) fn set_keepalive_params(
+    _: &net::TcpStream,
+    _: crate::net::TcpKeepalive,
+) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub(crate) fn keepalive_time(_: &net::TcpStream) -> io::Result<std::time::Duration> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub(crate) fn keepalive_interval(_: &net::TcpStream) -> io::Result<std::time::Duration> {
+    os_required!();
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "wasi")))]
+pub(crate) fn keepalive_retries(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout(
+    _: &net::TcpStream,
+    _: Option<std::time::Duration>,
+) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn user_timeout(_: &net::TcpStream) -> io::Result<Option<std::time::Duration>> {
+    os_required!();
+}
+
+cfg_sendfile! {
+    pub(crate) fn sendfile(
+        _: &net::TcpStream,
+        _: &std::fs::File,
+        _: u64,
+        _: usize,
+    ) -> io::Result<usize> {
+        os_required!();
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn set_max_segment_size(_: &net::TcpStream, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn max_segment_size(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn set_congestion(_: &net::TcpStream, _: &str) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn congestion(_: &net::TcpStream) -> io::Result<String> {
+    os_required!();
+}
+
+cfg_tcp_info! {
+    pub(crate) fn tcp_info(_: &net::TcpStream) -> io::Result<crate::net::TcpInfo> {
+        os_required!();
+    }
+}
+
 pub(crate) fn accept(_: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     os_required!();
 }
@@ -72,4 +72,15 @@ pub(crate) mod stream {
     pub(crate) fn peer_addr(_: &net::UnixStream) -> io::Result<SocketAddr> {
         os_required!()
     }
+
+    cfg_sendfile! {
+        pub(crate) fn sendfile(
+            _: &net::UnixStream,
+            _: &std::fs::File,
+            _: u64,
+            _: usize,
+        ) -> io::Result<usize> {
+            os_required!()
+        }
+    }
 }
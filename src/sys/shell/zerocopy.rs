@@ -0,0 +1,21 @@
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+
+use crate::net::ZerocopyCompletion;
+
+pub(crate) fn set_zerocopy(_: &impl AsRawFd, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+pub(crate) fn zerocopy(_: &impl AsRawFd) -> io::Result<bool> {
+    os_required!()
+}
+
+pub(crate) fn send(_: &impl AsRawFd, _: &[u8], _: Option<SocketAddr>) -> io::Result<usize> {
+    os_required!()
+}
+
+pub(crate) fn recv_completion(_: &impl AsRawFd) -> io::Result<ZerocopyCompletion> {
+    os_required!()
+}
@@ -0,0 +1,343 @@
+//! Linux native asynchronous I/O.
+//!
+//! See the [`Context`] type for documentation.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// `aio_context_t`.
+type AioContext = libc::c_ulong;
+
+// Values from `linux/aio_abi.h`.
+const IOCB_CMD_PREAD: u16 = 0;
+const IOCB_CMD_PWRITE: u16 = 1;
+const IOCB_FLAG_RESFD: u32 = 1 << 0;
+
+/// `struct iocb`, see `linux/aio_abi.h`.
+#[repr(C)]
+#[derive(Default)]
+struct Iocb {
+    aio_data: u64,
+    #[cfg(target_endian = "little")]
+    aio_key: u32,
+    aio_rw_flags: libc::c_int,
+    #[cfg(target_endian = "big")]
+    aio_key: u32,
+    aio_lio_opcode: u16,
+    aio_reqprio: i16,
+    aio_fildes: u32,
+    aio_buf: u64,
+    aio_nbytes: u64,
+    aio_offset: i64,
+    aio_reserved2: u64,
+    aio_flags: u32,
+    aio_resfd: u32,
+}
+
+/// `struct io_event`, see `linux/aio_abi.h`.
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+struct IoEvent {
+    data: u64,
+    obj: u64,
+    res: i64,
+    res2: i64,
+}
+
+/// Maximum number of completions retrieved in a single `io_getevents(2)` call.
+const REAP_BATCH: usize = 32;
+
+/// Linux native asynchronous I/O context, using [`io_submit(2)`].
+///
+/// Read and write operations are submitted using [`submit_read`] and
+/// [`submit_write`]. Once an operation completes the kernel signals an
+/// eventfd, which is registered with [`Poll`] when the `Context` is registered.
+/// After receiving a [readable event] the completed operations can be
+/// retrieved using [`reap`].
+///
+/// Files should be opened using `O_DIRECT`, otherwise the kernel may perform
+/// the operation synchronously while submitting it. `O_DIRECT` comes with its
+/// own set of requirements, most notably buffers, offsets and lengths must be
+/// aligned to the logical block size of the underlying storage.
+///
+/// [`io_submit(2)`]: https://man7.org/linux/man-pages/man2/io_submit.2.html
+/// [`submit_read`]: Context::submit_read
+/// [`submit_write`]: Context::submit_write
+/// [`Poll`]: crate::Poll
+/// [readable event]: event::Event::is_readable
+/// [`reap`]: Context::reap
+///
+/// # Notes
+///
+/// Dropping the `Context` blocks until all in-flight operations have
+/// completed, see [`io_destroy(2)`].
+///
+/// [`io_destroy(2)`]: https://man7.org/linux/man-pages/man2/io_destroy.2.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::fs::File;
+/// use std::os::unix::io::AsRawFd;
+///
+/// use mio::unix::aio::Context;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut aio = Context::new(16)?;
+/// poll.registry().register(&mut aio, Token(0), Interest::READABLE)?;
+///
+/// let file = File::open("Cargo.toml")?;
+/// let mut buf = vec![0; 4096];
+/// // Safety: `buf` and `file` outlive the operation as we wait for it to
+/// // complete below.
+/// unsafe { aio.submit_read(file.as_raw_fd(), buf.as_mut_ptr(), buf.len(), 0, 1)? };
+///
+/// let mut completions = Vec::new();
+/// while completions.is_empty() {
+///     poll.poll(&mut events, None)?;
+///     match aio.reap(&mut completions) {
+///         Ok(_) => {}
+///         Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+///
+/// assert_eq!(completions[0].user_data(), 1);
+/// let n = completions[0].result()?;
+/// assert!(buf[..n].starts_with(b"[package]"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Context {
+    ctx: AioContext,
+    /// eventfd signalled on completion.
+    eventfd: IoSource<File>,
+}
+
+impl Context {
+    /// Create a new AIO context that can hold at least `max_events` in-flight
+    /// operations, see [`io_setup(2)`].
+    ///
+    /// [`io_setup(2)`]: https://man7.org/linux/man-pages/man2/io_setup.2.html
+    pub fn new(max_events: u32) -> io::Result<Context> {
+        let fd = syscall!(eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK))?;
+        // SAFETY: `eventfd(2)` ensures the fd is valid.
+        let eventfd = IoSource::new(unsafe { File::from_raw_fd(fd) });
+
+        let mut ctx: AioContext = 0;
+        syscall!(syscall(
+            libc::SYS_io_setup,
+            max_events as libc::c_uint,
+            &mut ctx as *mut AioContext
+        ))?;
+        Ok(Context { ctx, eventfd })
+    }
+
+    /// Submit a read of `len` bytes from `fd`, starting at `offset`, into
+    /// `buf`.
+    ///
+    /// `user_data` is returned in the [`Completion`] for this operation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `fd` and `buf` remain valid until the
+    /// operation is completed, i.e. returned by [`Context::reap`] or the
+    /// `Context` is dropped.
+    pub unsafe fn submit_read(
+        &self,
+        fd: RawFd,
+        buf: *mut u8,
+        len: usize,
+        offset: u64,
+        user_data: u64,
+    ) -> io::Result<()> {
+        self.submit(IOCB_CMD_PREAD, fd, buf as u64, len, offset, user_data)
+    }
+
+    /// Submit a write of `len` bytes from `buf` to `fd`, starting at
+    /// `offset`.
+    ///
+    /// `user_data` is returned in the [`Completion`] for this operation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `fd` and `buf` remain valid until the
+    /// operation is completed, i.e. returned by [`Context::reap`] or the
+    /// `Context` is dropped.
+    pub unsafe fn submit_write(
+        &self,
+        fd: RawFd,
+        buf: *const u8,
+        len: usize,
+        offset: u64,
+        user_data: u64,
+    ) -> io::Result<()> {
+        self.submit(IOCB_CMD_PWRITE, fd, buf as u64, len, offset, user_data)
+    }
+
+    fn submit(
+        &self,
+        opcode: u16,
+        fd: RawFd,
+        buf: u64,
+        len: usize,
+        offset: u64,
+        user_data: u64,
+    ) -> io::Result<()> {
+        let mut iocb = Iocb {
+            aio_data: user_data,
+            aio_lio_opcode: opcode,
+            aio_fildes: fd as u32,
+            aio_buf: buf,
+            aio_nbytes: len as u64,
+            aio_offset: offset as i64,
+            aio_flags: IOCB_FLAG_RESFD,
+            aio_resfd: self.eventfd.as_raw_fd() as u32,
+            ..Iocb::default()
+        };
+        // The kernel copies the `iocb` while submitting, so it doesn't have to
+        // outlive this call.
+        let mut iocbs = [&mut iocb as *mut Iocb];
+        syscall!(syscall(
+            libc::SYS_io_submit,
+            self.ctx,
+            1 as libc::c_long,
+            iocbs.as_mut_ptr()
+        ))
+        .map(|_| ())
+    }
+
+    /// Retrieve all completed operations, adding them to `completions`.
+    ///
+    /// Returns the number of completions added. If no operations completed
+    /// since the last call this returns a [`WouldBlock`] error, after which a
+    /// [readable event] will be returned once another operation completes.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [readable event]: event::Event::is_readable
+    pub fn reap(&self, completions: &mut Vec<Completion>) -> io::Result<usize> {
+        // Reset the eventfd's counter before retrieving the completions, so
+        // that operations completing after this are signalled again.
+        let mut count = [0; 8];
+        self.eventfd
+            .do_io_with(Interest::READABLE, |mut eventfd| eventfd.read(&mut count))?;
+
+        let mut events = [IoEvent::default(); REAP_BATCH];
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let mut total = 0;
+        loop {
+            let n = syscall!(syscall(
+                libc::SYS_io_getevents,
+                self.ctx,
+                0 as libc::c_long,
+                REAP_BATCH as libc::c_long,
+                events.as_mut_ptr(),
+                &timeout as *const libc::timespec
+            ))? as usize;
+            completions.extend(events[..n].iter().map(|event| Completion {
+                user_data: event.data,
+                res: event.res,
+            }));
+            total += n;
+            if n < REAP_BATCH {
+                return Ok(total);
+            }
+        }
+    }
+}
+
+impl event::Source for Context {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.eventfd.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.eventfd.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.eventfd.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.eventfd.pause(registry, token)
+    }
+}
+
+impl AsRawFd for Context {
+    /// Returns the eventfd used to signal completions.
+    fn as_raw_fd(&self) -> RawFd {
+        self.eventfd.as_raw_fd()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("ctx", &self.ctx)
+            .field("eventfd", &self.eventfd)
+            .finish()
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        if let Err(err) = syscall!(syscall(libc::SYS_io_destroy, self.ctx)) {
+            error!("error destroying AIO context: {}", err);
+        }
+    }
+}
+
+/// A completed operation, returned by [`Context::reap`].
+#[derive(Debug)]
+pub struct Completion {
+    user_data: u64,
+    res: i64,
+}
+
+impl Completion {
+    /// Returns the `user_data` passed when submitting the operation.
+    pub fn user_data(&self) -> u64 {
+        self.user_data
+    }
+
+    /// Returns the result of the operation, the number of bytes read or
+    /// written.
+    pub fn result(&self) -> io::Result<usize> {
+        if self.res < 0 {
+            Err(io::Error::from_raw_os_error(-self.res as i32))
+        } else {
+            Ok(self.res as usize)
+        }
+    }
+}
+
+#[test]
+fn iocb_layout() {
+    // Must match `struct iocb` in `linux/aio_abi.h`.
+    assert_eq!(std::mem::size_of::<Iocb>(), 64);
+    assert_eq!(std::mem::size_of::<IoEvent>(), 32);
+}
@@ -0,0 +1,172 @@
+//! Event notification counter.
+//!
+//! See the [`EventFd`] type for documentation.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// Event notification counter using Linux's [`eventfd(2)`].
+///
+/// An eventfd holds a 64 bit counter. [`add`] adds to the counter and
+/// [`read`] retrieves it, how depends on the mode:
+///
+///  * In normal mode, created using [`EventFd::new`], `read` returns the
+///    value of the counter and resets it to zero.
+///  * In semaphore mode, created using [`EventFd::new_semaphore`], `read`
+///    returns one and decrements the counter by one.
+///
+/// Unlike [`Waker`] the file descriptor can be shared, e.g. with C code or
+/// another process. The file descriptor is created with the close-on-exec
+/// flag set, which must be cleared to pass it to a child process.
+///
+/// [`eventfd(2)`]: https://man7.org/linux/man-pages/man2/eventfd.2.html
+/// [`add`]: EventFd::add
+/// [`read`]: EventFd::read
+/// [`Waker`]: crate::Waker
+///
+/// # Events
+///
+/// Registering the `EventFd` with [`READABLE`] interest returns a [readable
+/// event] once the counter is larger than zero, [`WRITABLE`] interest returns
+/// a [writable event] once at least one can be added to the counter without
+/// overflowing.
+///
+/// [`READABLE`]: Interest::READABLE
+/// [readable event]: event::Event::is_readable
+/// [`WRITABLE`]: Interest::WRITABLE
+/// [writable event]: event::Event::is_writable
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::EventFd;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut eventfd = EventFd::new(0)?;
+/// poll.registry().register(&mut eventfd, Token(0), Interest::READABLE)?;
+///
+/// eventfd.add(2)?;
+/// eventfd.add(3)?;
+///
+/// poll.poll(&mut events, None)?;
+/// assert_eq!(eventfd.read()?, 5);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EventFd {
+    inner: IoSource<File>,
+}
+
+impl EventFd {
+    /// Create a new non-blocking eventfd in normal mode, with the counter set
+    /// to `initial`.
+    pub fn new(initial: u32) -> io::Result<EventFd> {
+        EventFd::with_flags(initial, 0)
+    }
+
+    /// Create a new non-blocking eventfd in semaphore mode (`EFD_SEMAPHORE`),
+    /// with the counter set to `initial`.
+    pub fn new_semaphore(initial: u32) -> io::Result<EventFd> {
+        EventFd::with_flags(initial, libc::EFD_SEMAPHORE)
+    }
+
+    fn with_flags(initial: u32, flags: libc::c_int) -> io::Result<EventFd> {
+        let flags = flags | libc::EFD_CLOEXEC | libc::EFD_NONBLOCK;
+        let fd = syscall!(eventfd(initial, flags))?;
+        // SAFETY: `eventfd(2)` ensures the fd is valid.
+        Ok(unsafe { EventFd::from_raw_fd(fd) })
+    }
+
+    /// Read the counter.
+    ///
+    /// In normal mode this returns the value of the counter and resets it to
+    /// zero, in semaphore mode this returns one and decrements the counter.
+    /// If the counter is zero this returns a [`WouldBlock`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn read(&self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        self.inner
+            .do_io_with(Interest::READABLE, |mut file| file.read(&mut buf))?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+
+    /// Add `value` to the counter.
+    ///
+    /// If the counter would overflow (exceed `u64::MAX - 1`) this returns a
+    /// [`WouldBlock`] error, a `value` of `u64::MAX` returns an
+    /// [`InvalidInput`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn add(&self, value: u64) -> io::Result<()> {
+        let buf = value.to_ne_bytes();
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut file| file.write(&buf))
+            .map(|_| ())
+    }
+}
+
+impl event::Source for EventFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
+}
+
+impl AsRawFd for EventFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for EventFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl FromRawFd for EventFd {
+    /// Converts a `RawFd` to a `EventFd`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the eventfd is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> EventFd {
+        EventFd {
+            inner: IoSource::new(File::from_raw_fd(fd)),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::sys::unix::net::{new_socket_with_protocol, socket_addr, to_socket_addr};
+
+use std::io;
+use std::mem::{self, size_of};
+use std::net::{IpAddr, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+pub(crate) fn new(ipv6: bool, socket_type: libc::c_int) -> io::Result<OwnedFd> {
+    let (domain, protocol) = if ipv6 {
+        (libc::AF_INET6, libc::IPPROTO_ICMPV6)
+    } else {
+        (libc::AF_INET, libc::IPPROTO_ICMP)
+    };
+    let fd = new_socket_with_protocol(domain, socket_type, protocol)?;
+    // SAFETY: `new_socket_with_protocol` ensures the fd is valid.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+pub(crate) fn bind(socket: &OwnedFd, addr: IpAddr) -> io::Result<()> {
+    let (raw_addr, raw_addr_length) = socket_addr(&SocketAddr::new(addr, 0));
+    syscall!(bind(socket.as_raw_fd(), raw_addr.as_ptr(), raw_addr_length)).map(|_| ())
+}
+
+pub(crate) fn connect(socket: &OwnedFd, addr: IpAddr) -> io::Result<()> {
+    let (raw_addr, raw_addr_length) = socket_addr(&SocketAddr::new(addr, 0));
+    syscall!(connect(
+        socket.as_raw_fd(),
+        raw_addr.as_ptr(),
+        raw_addr_length
+    ))
+    .map(|_| ())
+}
+
+pub(crate) fn local_addr(socket: &OwnedFd) -> io::Result<IpAddr> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockname(
+        socket.as_raw_fd(),
+        &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+        &mut length
+    ))?;
+    // SAFETY: `getsockname` initialised `storage` for us.
+    unsafe { to_socket_addr(&storage) }.map(|addr| addr.ip())
+}
+
+pub(crate) fn send_to(socket: &OwnedFd, buf: &[u8], target: IpAddr) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&SocketAddr::new(target, 0));
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr().cast(),
+        buf.len(),
+        0,
+        raw_addr.as_ptr(),
+        raw_addr_length
+    ))
+    .map(|n| n as usize)
+}
+
+pub(crate) fn send(socket: &OwnedFd, buf: &[u8]) -> io::Result<usize> {
+    syscall!(send(socket.as_raw_fd(), buf.as_ptr().cast(), buf.len(), 0)).map(|n| n as usize)
+}
+
+pub(crate) fn recv_from(
+    socket: &OwnedFd,
+    buf: &mut [u8],
+    flags: libc::c_int,
+) -> io::Result<(usize, IpAddr)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        flags,
+        &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+        &mut length
+    ))?;
+    // SAFETY: `recvfrom` initialised `storage` for us.
+    let addr = unsafe { to_socket_addr(&storage) }?;
+    Ok((n as usize, addr.ip()))
+}
@@ -0,0 +1,275 @@
+//! File change notifications using inotify.
+//!
+//! See the [`Inotify`] type for documentation.
+
+use std::ffi::{CString, OsStr};
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem::size_of;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::path::Path;
+use std::ptr;
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// File change notifications using Linux's [`inotify(7)`] API.
+///
+/// Files and directories can be watched using [`add_watch`], after which
+/// events are queued in the kernel. Registering the `Inotify` with
+/// [`READABLE`] interest will return a [readable event] once events are
+/// queued, which can then be read using [`read_events`].
+///
+/// The watch masks (e.g. `IN_MODIFY`) and the masks of the returned events
+/// are the raw values as defined by inotify, they can be found in the `libc`
+/// crate.
+///
+/// [`inotify(7)`]: https://man7.org/linux/man-pages/man7/inotify.7.html
+/// [`add_watch`]: Inotify::add_watch
+/// [`READABLE`]: Interest::READABLE
+/// [readable event]: event::Event::is_readable
+/// [`read_events`]: Inotify::read_events
+///
+/// # Notes
+///
+/// If the kernel's event queue overflows, events are lost and an event for
+/// which [`InotifyEvent::is_overflow`] returns true is returned instead.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::unix::Inotify;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut inotify = Inotify::new()?;
+/// let dir = std::env::temp_dir();
+/// inotify.add_watch(&dir, libc::IN_CREATE | libc::IN_MODIFY)?;
+/// poll.registry().register(&mut inotify, Token(0), Interest::READABLE)?;
+///
+/// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+/// if !events.is_empty() {
+///     let mut buf = [0; 4096];
+///     for event in inotify.read_events(&mut buf)? {
+///         println!("{:?} changed in {:?}", event.name(), dir);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Inotify {
+    inner: IoSource<File>,
+}
+
+/// Identifier of a watch added using [`Inotify::add_watch`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WatchDescriptor(libc::c_int);
+
+impl Inotify {
+    /// Create a new non-blocking inotify instance.
+    pub fn new() -> io::Result<Inotify> {
+        let fd = syscall!(inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC))?;
+        // SAFETY: `inotify_init1(2)` ensures the fd is valid.
+        Ok(unsafe { Inotify::from_raw_fd(fd) })
+    }
+
+    /// Add a watch for `path`, or update the existing watch if `path` is
+    /// already watched, using the events in `mask` (e.g. `IN_MODIFY`).
+    ///
+    /// Returns the descriptor used for the watch, which is also set in the
+    /// events for it, see [`InotifyEvent::wd`].
+    pub fn add_watch<P: AsRef<Path>>(&self, path: P, mask: u32) -> io::Result<WatchDescriptor> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains a nul byte"))?;
+        syscall!(inotify_add_watch(
+            self.inner.as_raw_fd(),
+            path.as_ptr(),
+            mask
+        ))
+        .map(WatchDescriptor)
+    }
+
+    /// Remove the watch `wd`.
+    ///
+    /// This causes an `IN_IGNORED` event to be generated for the watch.
+    pub fn remove_watch(&self, wd: WatchDescriptor) -> io::Result<()> {
+        syscall!(inotify_rm_watch(self.inner.as_raw_fd(), wd.0)).map(|_| ())
+    }
+
+    /// Read the queued events into `buf`, returning an iterator over them.
+    ///
+    /// If no events are queued this returns a [`WouldBlock`] error, after
+    /// which a [readable event] will be returned once more events are queued.
+    ///
+    /// `buf` must be large enough to hold at least a single event, which is
+    /// `size_of::<libc::inotify_event>() + NAME_MAX + 1` bytes, otherwise an
+    /// [`InvalidInput`] error is returned. A buffer of 4096 bytes is a good
+    /// default.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [readable event]: event::Event::is_readable
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    pub fn read_events<'a>(&self, buf: &'a mut [u8]) -> io::Result<InotifyEvents<'a>> {
+        let n = self
+            .inner
+            .do_io_with(Interest::READABLE, |mut file| file.read(buf))?;
+        Ok(InotifyEvents {
+            buf: &buf[..n],
+            pos: 0,
+        })
+    }
+}
+
+impl event::Source for Inotify {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
+}
+
+impl AsRawFd for Inotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Inotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+impl FromRawFd for Inotify {
+    /// Converts a `RawFd` to a `Inotify`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the inotify instance is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> Inotify {
+        Inotify {
+            inner: IoSource::new(File::from_raw_fd(fd)),
+        }
+    }
+}
+
+/// Iterator over the events read using [`Inotify::read_events`].
+#[derive(Debug)]
+pub struct InotifyEvents<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for InotifyEvents<'a> {
+    type Item = InotifyEvent<'a>;
+
+    fn next(&mut self) -> Option<InotifyEvent<'a>> {
+        const HEADER_SIZE: usize = size_of::<libc::inotify_event>();
+
+        let buf = &self.buf[self.pos..];
+        if buf.len() < HEADER_SIZE {
+            // The kernel only returns complete events.
+            return None;
+        }
+
+        // SAFETY: we checked above that the buffer is large enough, using an
+        // unaligned read as the buffer only has an alignment of 1.
+        let header: libc::inotify_event = unsafe { ptr::read_unaligned(buf.as_ptr().cast()) };
+        let name_len = header.len as usize;
+        let name = buf.get(HEADER_SIZE..HEADER_SIZE + name_len)?;
+        self.pos += HEADER_SIZE + name_len;
+
+        // The name is padded with null bytes.
+        let name_end = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+        Some(InotifyEvent {
+            wd: WatchDescriptor(header.wd),
+            mask: header.mask,
+            cookie: header.cookie,
+            name: if name_end == 0 {
+                None
+            } else {
+                Some(OsStr::from_bytes(&name[..name_end]))
+            },
+        })
+    }
+}
+
+/// A single inotify event, see [`Inotify::read_events`].
+#[derive(Clone, Debug)]
+pub struct InotifyEvent<'a> {
+    wd: WatchDescriptor,
+    mask: u32,
+    cookie: u32,
+    name: Option<&'a OsStr>,
+}
+
+impl<'a> InotifyEvent<'a> {
+    /// Returns the watch the event is for.
+    pub fn wd(&self) -> WatchDescriptor {
+        self.wd
+    }
+
+    /// Returns the mask describing the event, e.g. `IN_MODIFY`.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Returns the cookie used to connect related events, e.g. `IN_MOVED_FROM`
+    /// and `IN_MOVED_TO` events for the same rename have the same cookie.
+    pub fn cookie(&self) -> u32 {
+        self.cookie
+    }
+
+    /// Returns the name of the file the event is for, relative to the watched
+    /// directory. Returns `None` for events for the watched file or directory
+    /// itself.
+    pub fn name(&self) -> Option<&'a OsStr> {
+        self.name
+    }
+
+    /// Returns true if the event is for a directory (`IN_ISDIR`).
+    pub fn is_dir(&self) -> bool {
+        self.mask & libc::IN_ISDIR != 0
+    }
+
+    /// Returns true if the kernel's event queue overflowed
+    /// (`IN_Q_OVERFLOW`), meaning that events were lost.
+    pub fn is_overflow(&self) -> bool {
+        self.mask & libc::IN_Q_OVERFLOW != 0
+    }
+
+    /// Returns true if the watch was removed (`IN_IGNORED`), either explicitly
+    /// using [`Inotify::remove_watch`] or because the file was deleted.
+    pub fn is_ignored(&self) -> bool {
+        self.mask & libc::IN_IGNORED != 0
+    }
+}
@@ -16,6 +16,9 @@ macro_rules! syscall {
 cfg_os_poll! {
     mod selector;
     pub(crate) use self::selector::{event, Event, Events, Selector};
+    cfg_os_ext! {
+        pub use self::selector::RawEvent;
+    }
 
     mod sourcefd;
     pub use self::sourcefd::SourceFd;
@@ -26,10 +29,20 @@ cfg_os_poll! {
     cfg_net! {
         mod net;
 
+        pub(crate) mod icmp;
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub(crate) mod netlink;
+        #[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+        pub(crate) mod reuseport;
+        cfg_sendfile! {
+            mod sendfile;
+        }
         pub(crate) mod tcp;
         pub(crate) mod udp;
         pub(crate) mod uds;
         pub use self::uds::SocketAddr;
+        #[cfg(target_os = "linux")]
+        pub(crate) mod zerocopy;
     }
 
     cfg_io_source! {
@@ -56,6 +69,19 @@ cfg_os_poll! {
 
     cfg_os_ext! {
         pub(crate) mod pipe;
+
+        #[cfg(target_os = "linux")]
+        pub(crate) mod aio;
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub(crate) mod eventfd;
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub(crate) mod inotify;
+
+        pub(crate) mod process;
+        pub(crate) mod stdio;
+        pub(crate) mod tty;
     }
 }
 
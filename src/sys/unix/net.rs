@@ -1,6 +1,28 @@
 use std::io;
-use std::mem::size_of;
+use std::mem::{size_of, MaybeUninit};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
+
+// Socket options the libc crate doesn't define for Linux (`SO_INCOMING_CPU` is
+// missing for Android as well). SPARC uses its own values.
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    not(target_arch = "sparc64")
+))]
+pub(crate) const SO_INCOMING_CPU: libc::c_int = 49;
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    target_arch = "sparc64"
+))]
+pub(crate) const SO_INCOMING_CPU: libc::c_int = 0x33;
+#[cfg(all(target_os = "linux", not(target_arch = "sparc64")))]
+pub(crate) const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 51;
+#[cfg(all(target_os = "linux", target_arch = "sparc64"))]
+pub(crate) const SO_ATTACH_REUSEPORT_CBPF: libc::c_int = 0x35;
+#[cfg(all(target_os = "linux", not(target_arch = "sparc64")))]
+pub(crate) const SO_ZEROCOPY: libc::c_int = 60;
+#[cfg(all(target_os = "linux", target_arch = "sparc64"))]
+pub(crate) const SO_ZEROCOPY: libc::c_int = 0x3e;
 
 pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
     let domain = match addr {
@@ -13,6 +35,15 @@ pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::R
 
 /// Create a new non-blocking socket.
 pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<libc::c_int> {
+    new_socket_with_protocol(domain, socket_type, 0)
+}
+
+/// Create a new non-blocking socket using a specific `protocol`.
+pub(crate) fn new_socket_with_protocol(
+    domain: libc::c_int,
+    socket_type: libc::c_int,
+    protocol: libc::c_int,
+) -> io::Result<libc::c_int> {
     #[cfg(any(
         target_os = "android",
         target_os = "dragonfly",
@@ -24,7 +55,7 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     ))]
     let socket_type = socket_type | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC;
 
-    let socket = syscall!(socket(domain, socket_type, 0))?;
+    let socket = syscall!(socket(domain, socket_type, protocol))?;
 
     // Mimick `libstd` and set `SO_NOSIGPIPE` on apple systems.
     #[cfg(any(
@@ -65,6 +96,39 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     Ok(socket)
 }
 
+/// Set the socket option `name` at `level` to `value`.
+pub(crate) fn set_socket_option<T>(
+    fd: RawFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))?;
+    Ok(())
+}
+
+/// Get the value of the socket option `name` at `level`.
+pub(crate) fn socket_option<T>(fd: RawFd, level: libc::c_int, name: libc::c_int) -> io::Result<T> {
+    let mut value: MaybeUninit<T> = MaybeUninit::uninit();
+    let mut length = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        level,
+        name,
+        value.as_mut_ptr().cast(),
+        &mut length,
+    ))?;
+    debug_assert_eq!(length as usize, size_of::<T>());
+    // SAFETY: `getsockopt` initialised `value` for us.
+    Ok(unsafe { value.assume_init() })
+}
+
 /// A type with the same memory layout as `libc::sockaddr`. Used in converting Rust level
 /// SocketAddr* types into their system representation. The benefit of this specific
 /// type over using `libc::sockaddr_storage` is that this type is exactly as large as it
@@ -0,0 +1,127 @@
+use crate::sys::unix::net::new_socket_with_protocol;
+
+use std::fmt;
+use std::io;
+use std::mem::{self, size_of};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+/// An address of a netlink socket.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NetlinkAddr {
+    port_id: u32,
+    groups: u32,
+}
+
+impl NetlinkAddr {
+    /// Create a new netlink address.
+    ///
+    /// `port_id` identifies the socket, zero refers to the kernel when used as
+    /// destination or lets the kernel assign an id when binding. `groups` is
+    /// a bit mask of multicast groups, e.g. `RTMGRP_LINK`.
+    pub fn new(port_id: u32, groups: u32) -> NetlinkAddr {
+        NetlinkAddr { port_id, groups }
+    }
+
+    /// Returns the port id of the address.
+    pub fn port_id(&self) -> u32 {
+        self.port_id
+    }
+
+    /// Returns the multicast groups bit mask of the address.
+    pub fn groups(&self) -> u32 {
+        self.groups
+    }
+
+    fn to_sockaddr(self) -> libc::sockaddr_nl {
+        let mut addr: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_pid = self.port_id;
+        addr.nl_groups = self.groups;
+        addr
+    }
+
+    fn from_sockaddr(addr: &libc::sockaddr_nl) -> NetlinkAddr {
+        NetlinkAddr::new(addr.nl_pid, addr.nl_groups)
+    }
+}
+
+impl fmt::Debug for NetlinkAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetlinkAddr")
+            .field("port_id", &self.port_id)
+            .field("groups", &format_args!("{:#x}", self.groups))
+            .finish()
+    }
+}
+
+pub(crate) fn bind(protocol: libc::c_int, addr: NetlinkAddr) -> io::Result<OwnedFd> {
+    let fd = new_socket_with_protocol(libc::AF_NETLINK, libc::SOCK_RAW, protocol)?;
+    // SAFETY: `new_socket_with_protocol` ensures the fd is valid.
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let raw_addr = addr.to_sockaddr();
+    syscall!(bind(
+        fd,
+        &raw_addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+        size_of::<libc::sockaddr_nl>() as libc::socklen_t
+    ))?;
+    Ok(socket)
+}
+
+pub(crate) fn local_addr(socket: &OwnedFd) -> io::Result<NetlinkAddr> {
+    let mut raw_addr: libc::sockaddr_nl = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_nl>() as libc::socklen_t;
+    syscall!(getsockname(
+        socket.as_raw_fd(),
+        &mut raw_addr as *mut libc::sockaddr_nl as *mut libc::sockaddr,
+        &mut length
+    ))?;
+    Ok(NetlinkAddr::from_sockaddr(&raw_addr))
+}
+
+pub(crate) fn send_to(socket: &OwnedFd, buf: &[u8], addr: NetlinkAddr) -> io::Result<usize> {
+    let raw_addr = addr.to_sockaddr();
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr().cast(),
+        buf.len(),
+        libc::MSG_NOSIGNAL,
+        &raw_addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+        size_of::<libc::sockaddr_nl>() as libc::socklen_t
+    ))
+    .map(|n| n as usize)
+}
+
+pub(crate) fn recv_from(
+    socket: &OwnedFd,
+    buf: &mut [u8],
+    flags: libc::c_int,
+) -> io::Result<(usize, NetlinkAddr)> {
+    let mut raw_addr: libc::sockaddr_nl = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_nl>() as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        flags,
+        &mut raw_addr as *mut libc::sockaddr_nl as *mut libc::sockaddr,
+        &mut length
+    ))?;
+    Ok((n as usize, NetlinkAddr::from_sockaddr(&raw_addr)))
+}
+
+pub(crate) fn set_membership(socket: &OwnedFd, group: u32, join: bool) -> io::Result<()> {
+    let option = if join {
+        libc::NETLINK_ADD_MEMBERSHIP
+    } else {
+        libc::NETLINK_DROP_MEMBERSHIP
+    };
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_NETLINK,
+        option,
+        &group as *const u32 as *const libc::c_void,
+        size_of::<u32>() as libc::socklen_t
+    ))
+    .map(|_| ())
+}
@@ -6,6 +6,8 @@ use std::fs::File;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::process::{ChildStderr, ChildStdin, ChildStdout};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::ptr;
 
 use crate::io_source::IoSource;
 use crate::{event, Interest, Registry, Token};
@@ -21,6 +23,9 @@ use crate::{event, Interest, Registry, Token};
 ///
 /// [`pipe(2)`]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/pipe.html
 ///
+/// Use [`Builder`] to create a pipe with non-default options, such as its
+/// capacity.
+///
 /// # Events
 ///
 /// The [`Sender`] can be registered with [`WRITABLE`] interest to receive
@@ -212,6 +217,114 @@ pub fn new() -> io::Result<(Sender, Receiver)> {
     Ok((w, r))
 }
 
+/// Builder for Unix pipes with non-default options.
+///
+/// [`build`] creates the pipe, [`new`] is the same as building a pipe using
+/// the default options.
+///
+/// [`build`]: Builder::build
+///
+/// # Examples
+///
+/// Create a pipe in packet mode, preserving message boundaries.
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(target_os = "linux")] {
+/// use std::io::{Read, Write};
+///
+/// use mio::unix::pipe;
+///
+/// let (mut sender, mut receiver) = pipe::Builder::new()
+///     .capacity(128 * 1024)
+///     .packet_mode(true)
+///     .build()?;
+/// assert!(sender.capacity()? >= 128 * 1024);
+///
+/// sender.write_all(b"hello")?;
+/// sender.write_all(b"world")?;
+/// assert_eq!(receiver.pending_bytes()?, 10);
+///
+/// let mut buf = [0; 64];
+/// let n = receiver.read(&mut buf)?;
+/// assert_eq!(&buf[..n], b"hello");
+/// let n = receiver.read(&mut buf)?;
+/// assert_eq!(&buf[..n], b"world");
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    capacity: Option<usize>,
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    packet_mode: bool,
+}
+
+impl Builder {
+    /// Create a new `Builder` using the default options.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Set the capacity of the pipe, using `F_SETPIPE_SZ`, see [`fcntl(2)`].
+    ///
+    /// The kernel may round the capacity up, use [`Sender::capacity`] to
+    /// retrieve the actual capacity. Unprivileged processes can't set a
+    /// capacity larger than `/proc/sys/fs/pipe-max-size`.
+    ///
+    /// [`fcntl(2)`]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn capacity(mut self, capacity: usize) -> Builder {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Create the pipe in packet mode (`O_DIRECT`), see [`pipe(2)`].
+    ///
+    /// In packet mode each write is a separate packet and each read reads at
+    /// most one packet. If the buffer passed to read is smaller than the
+    /// packet the remainder of the packet is discarded. Writes larger than
+    /// `PIPE_BUF` bytes are split into multiple packets.
+    ///
+    /// [`pipe(2)`]: https://man7.org/linux/man-pages/man2/pipe.2.html
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn packet_mode(mut self, packet_mode: bool) -> Builder {
+        self.packet_mode = packet_mode;
+        self
+    }
+
+    /// Create the non-blocking pipe.
+    pub fn build(&self) -> io::Result<(Sender, Receiver)> {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            let (sender, receiver) = if self.packet_mode {
+                let mut fds: [RawFd; 2] = [-1, -1];
+                syscall!(pipe2(
+                    fds.as_mut_ptr(),
+                    libc::O_CLOEXEC | libc::O_NONBLOCK | libc::O_DIRECT
+                ))?;
+                // SAFETY: we just initialised the `fds` above.
+                let r = unsafe { Receiver::from_raw_fd(fds[0]) };
+                let w = unsafe { Sender::from_raw_fd(fds[1]) };
+                (w, r)
+            } else {
+                new()?
+            };
+            if let Some(capacity) = self.capacity {
+                let capacity = capacity.min(libc::c_int::MAX as usize) as libc::c_int;
+                syscall!(fcntl(sender.as_raw_fd(), libc::F_SETPIPE_SZ, capacity))?;
+            }
+            Ok((sender, receiver))
+        }
+
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        new()
+    }
+}
+
 /// Sending end of an Unix pipe.
 ///
 /// See [`new`] for documentation, including examples.
@@ -226,6 +339,29 @@ impl Sender {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
     }
 
+    /// Returns the number of bytes in the pipe, waiting to be read.
+    ///
+    /// Only Linux supports `FIONREAD` on the sending end of a pipe.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn pending_bytes(&self) -> io::Result<usize> {
+        pending_bytes(self.inner.as_raw_fd())
+    }
+
+    /// Returns the last known readiness of the pipe, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// # Notes
+    ///
+    /// `splice_from` (Android and Linux only) doesn't update the readiness, as a
+    /// `WouldBlock` error doesn't tell whether the source or this pipe wasn't
+    /// ready.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -295,6 +431,40 @@ impl Sender {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl Sender {
+    /// Returns the capacity of the pipe in bytes, using `F_GETPIPE_SZ`, see
+    /// [`Builder::capacity`].
+    pub fn capacity(&self) -> io::Result<usize> {
+        capacity(self.inner.as_raw_fd())
+    }
+
+    /// Move up to `len` bytes from `src` into the pipe, without copying them
+    /// through user space, using [`splice(2)`].
+    ///
+    /// `src` can be any file descriptor supported by `splice(2)`, such as a
+    /// [`TcpStream`] or [`UnixStream`], which should be in non-blocking mode.
+    /// Returns `Ok(0)` once `src` reaches end of file.
+    ///
+    /// If this returns a [`WouldBlock`] error either `src` has no data
+    /// available, or the pipe is full. To ensure no events are missed wait for
+    /// a [readable event] on `src` and a [writable event] on the `Sender`.
+    /// Because of this ambiguity this doesn't update the [`readiness`] of the
+    /// pipe.
+    ///
+    /// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+    /// [`TcpStream`]: crate::net::TcpStream
+    /// [`UnixStream`]: crate::net::UnixStream
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [readable event]: event::Event::is_readable
+    /// [writable event]: event::Event::is_writable
+    /// [`readiness`]: Sender::readiness
+    pub fn splice_from<S: AsRawFd + ?Sized>(&self, src: &S, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|sender| splice(src.as_raw_fd(), sender.as_raw_fd(), len))
+    }
+}
+
 impl event::Source for Sender {
     fn register(
         &mut self,
@@ -317,33 +487,43 @@ impl event::Source for Sender {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl Write for Sender {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut sender| sender.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.flush())
     }
 }
 
 impl Write for &Sender {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.write(buf))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.write(buf))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.write_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.write_vectored(bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.do_io(|mut sender| sender.flush())
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut sender| sender.flush())
     }
 }
 
@@ -391,6 +571,26 @@ impl Receiver {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
     }
 
+    /// Returns the number of bytes in the pipe, waiting to be read.
+    pub fn pending_bytes(&self) -> io::Result<usize> {
+        pending_bytes(self.inner.as_raw_fd())
+    }
+
+    /// Returns the last known readiness of the pipe, or `None` if it's
+    /// known to be neither readable nor writable. See [readiness cache] for
+    /// how this is determined.
+    ///
+    /// # Notes
+    ///
+    /// `splice_to` and `tee` (Android and Linux only) don't update the readiness,
+    /// as a `WouldBlock` error doesn't tell whether this pipe or the
+    /// destination wasn't ready.
+    ///
+    /// [readiness cache]: crate::Poll#readiness-cache
+    pub fn readiness(&self) -> Option<Interest> {
+        self.inner.readiness()
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -460,6 +660,64 @@ impl Receiver {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl Receiver {
+    /// Returns the capacity of the pipe in bytes, using `F_GETPIPE_SZ`, see
+    /// [`Builder::capacity`].
+    pub fn capacity(&self) -> io::Result<usize> {
+        capacity(self.inner.as_raw_fd())
+    }
+
+    /// Move up to `len` bytes from the pipe into `dst`, without copying them
+    /// through user space, using [`splice(2)`].
+    ///
+    /// `dst` can be any file descriptor supported by `splice(2)`, such as a
+    /// [`TcpStream`] or [`UnixStream`], which should be in non-blocking mode.
+    /// Returns `Ok(0)` once the `Sender` is dropped and the pipe is empty.
+    ///
+    /// If this returns a [`WouldBlock`] error either the pipe is empty, or
+    /// `dst` can't accept more data. To ensure no events are missed wait for a
+    /// [readable event] on the `Receiver` and a [writable event] on `dst`.
+    /// Because of this ambiguity this doesn't update the [`readiness`] of the
+    /// pipe.
+    ///
+    /// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+    /// [`TcpStream`]: crate::net::TcpStream
+    /// [`UnixStream`]: crate::net::UnixStream
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [readable event]: event::Event::is_readable
+    /// [writable event]: event::Event::is_writable
+    /// [`readiness`]: Receiver::readiness
+    pub fn splice_to<D: AsRawFd + ?Sized>(&self, dst: &D, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|receiver| splice(receiver.as_raw_fd(), dst.as_raw_fd(), len))
+    }
+
+    /// Duplicate up to `len` bytes from the pipe into the pipe of `dst`,
+    /// without consuming them, using [`tee(2)`].
+    ///
+    /// The data remains available to be read from this `Receiver`. Just like
+    /// [`splice_to`] a [`WouldBlock`] error means that either this pipe is
+    /// empty or the pipe of `dst` is full, and it doesn't update the
+    /// [`readiness`] of either pipe.
+    ///
+    /// [`tee(2)`]: https://man7.org/linux/man-pages/man2/tee.2.html
+    /// [`splice_to`]: Receiver::splice_to
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`readiness`]: Receiver::readiness
+    pub fn tee(&self, dst: &Sender, len: usize) -> io::Result<usize> {
+        self.inner.do_io(|receiver| {
+            let res = syscall!(tee(
+                receiver.as_raw_fd(),
+                dst.as_raw_fd(),
+                len,
+                libc::SPLICE_F_NONBLOCK
+            ))?;
+            Ok(res as usize)
+        })
+    }
+}
+
 impl event::Source for Receiver {
     fn register(
         &mut self,
@@ -482,25 +740,33 @@ impl event::Source for Receiver {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)
+    }
 }
 
 impl Read for Receiver {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut sender| sender.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut sender| sender.read_vectored(bufs))
     }
 }
 
 impl Read for &Receiver {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.read(buf))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut sender| sender.read(buf))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|mut sender| sender.read_vectored(bufs))
+        self.inner
+            .do_io_with(Interest::READABLE, |mut sender| sender.read_vectored(bufs))
     }
 }
 
@@ -544,6 +810,221 @@ impl IntoRawFd for Receiver {
     }
 }
 
+fn pending_bytes(fd: RawFd) -> io::Result<usize> {
+    let mut pending: libc::c_int = 0;
+    syscall!(ioctl(fd, libc::FIONREAD, &mut pending))?;
+    Ok(pending as usize)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn capacity(fd: RawFd) -> io::Result<usize> {
+    syscall!(fcntl(fd, libc::F_GETPIPE_SZ)).map(|capacity| capacity as usize)
+}
+
+/// Move up to `len` bytes from `fd_in` to `fd_out`, at least one of which must
+/// be a pipe.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn splice(fd_in: RawFd, fd_out: RawFd, len: usize) -> io::Result<usize> {
+    let res = syscall!(splice(
+        fd_in,
+        ptr::null_mut(),
+        fd_out,
+        ptr::null_mut(),
+        len,
+        libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK
+    ))?;
+    Ok(res as usize)
+}
+
+/// Copies data in both directions between two file descriptors, without
+/// copying it through user space.
+///
+/// This uses a pair of pipes as intermediate buffers, moving data from and
+/// into them using [`splice(2)`]. This is useful for e.g. proxying between two
+/// [`TcpStream`]s.
+///
+/// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+/// [`TcpStream`]: crate::net::TcpStream
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{Read, Write};
+///
+/// use mio::net::UnixStream;
+/// use mio::unix::pipe::CopyBidirectional;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// // Proxy between `a2` and `b1`.
+/// let (mut a1, mut a2) = UnixStream::pair()?;
+/// let (mut b1, mut b2) = UnixStream::pair()?;
+/// poll.registry().register(&mut a2, Token(0), Interest::READABLE | Interest::WRITABLE)?;
+/// poll.registry().register(&mut b1, Token(1), Interest::READABLE | Interest::WRITABLE)?;
+///
+/// a1.write_all(b"ping")?;
+/// a1.shutdown(std::net::Shutdown::Write)?;
+/// b2.write_all(b"pong")?;
+/// b2.shutdown(std::net::Shutdown::Write)?;
+///
+/// let mut copy = CopyBidirectional::new()?;
+/// // Returns false until both directions reached end of file.
+/// while !copy.copy(&a2, &b1)? {
+///     poll.poll(&mut events, None)?;
+/// }
+/// assert_eq!(copy.a_to_b(), 4);
+/// assert_eq!(copy.b_to_a(), 4);
+///
+/// let mut buf = [0; 4];
+/// b2.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"ping");
+/// a1.read_exact(&mut buf)?;
+/// assert_eq!(&buf, b"pong");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Debug)]
+pub struct CopyBidirectional {
+    a_to_b: SpliceDirection,
+    b_to_a: SpliceDirection,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl CopyBidirectional {
+    /// Create a new `CopyBidirectional`, creating the intermediate pipes.
+    pub fn new() -> io::Result<CopyBidirectional> {
+        Ok(CopyBidirectional {
+            a_to_b: SpliceDirection::new()?,
+            b_to_a: SpliceDirection::new()?,
+        })
+    }
+
+    /// Copy as much data as possible from `a` to `b`, and from `b` to `a`.
+    ///
+    /// Returns `Ok(true)` once both `a` and `b` reached end of file and all
+    /// data is copied. Once one direction is done the writing side of the
+    /// destination is shut down, if it's a socket.
+    ///
+    /// Returns `Ok(false)` if no more progress can be made without blocking,
+    /// in which case this should be called again after receiving an event for
+    /// either `a` or `b`. Both should be registered with [`READABLE`] and
+    /// [`WRITABLE`] interests.
+    ///
+    /// The same `a` and `b` must be used for all calls.
+    ///
+    /// [`READABLE`]: Interest::READABLE
+    /// [`WRITABLE`]: Interest::WRITABLE
+    pub fn copy<A, B>(&mut self, a: &A, b: &B) -> io::Result<bool>
+    where
+        A: AsRawFd + ?Sized,
+        B: AsRawFd + ?Sized,
+    {
+        let a_to_b = self.a_to_b.transfer(a.as_raw_fd(), b.as_raw_fd())?;
+        let b_to_a = self.b_to_a.transfer(b.as_raw_fd(), a.as_raw_fd())?;
+        Ok(a_to_b && b_to_a)
+    }
+
+    /// Returns the number of bytes copied from `a` to `b`.
+    pub fn a_to_b(&self) -> u64 {
+        self.a_to_b.transferred
+    }
+
+    /// Returns the number of bytes copied from `b` to `a`.
+    pub fn b_to_a(&self) -> u64 {
+        self.b_to_a.transferred
+    }
+}
+
+/// A single direction of [`CopyBidirectional`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Debug)]
+struct SpliceDirection {
+    sender: Sender,
+    receiver: Receiver,
+    /// Number of bytes in the pipe.
+    buffered: usize,
+    /// Number of bytes written to the destination.
+    transferred: u64,
+    /// Source reached end of file.
+    read_done: bool,
+    /// Destination was shut down, after all data was written.
+    done: bool,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl SpliceDirection {
+    /// Maximum number of bytes to move in a single `splice(2)` call, the
+    /// default capacity of a pipe.
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    fn new() -> io::Result<SpliceDirection> {
+        let (sender, receiver) = new()?;
+        Ok(SpliceDirection {
+            sender,
+            receiver,
+            buffered: 0,
+            transferred: 0,
+            read_done: false,
+            done: false,
+        })
+    }
+
+    /// Returns true once done.
+    fn transfer(&mut self, src: RawFd, dst: RawFd) -> io::Result<bool> {
+        while !self.done {
+            let mut progress = false;
+
+            if !self.read_done {
+                match splice(src, self.sender.as_raw_fd(), Self::CHUNK_SIZE) {
+                    Ok(0) => self.read_done = true,
+                    Ok(n) => {
+                        self.buffered += n;
+                        progress = true;
+                    }
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if self.buffered > 0 {
+                match splice(self.receiver.as_raw_fd(), dst, self.buffered) {
+                    Ok(n) => {
+                        self.buffered -= n;
+                        self.transferred += n as u64;
+                        progress = true;
+                    }
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if self.read_done && self.buffered == 0 {
+                match syscall!(shutdown(dst, libc::SHUT_WR)) {
+                    // Not a socket, or the peer is already gone.
+                    Ok(_) => {}
+                    Err(ref err)
+                        if matches!(
+                            err.raw_os_error(),
+                            Some(libc::ENOTSOCK) | Some(libc::ENOTCONN)
+                        ) => {}
+                    Err(err) => return Err(err),
+                }
+                self.done = true;
+            } else if !progress {
+                break;
+            }
+        }
+        Ok(self.done)
+    }
+}
+
 #[cfg(not(target_os = "illumos"))]
 fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
     let value = nonblocking as libc::c_int;
@@ -0,0 +1,531 @@
+//! Child processes.
+//!
+//! See the [`Command`] type for documentation.
+
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, Write};
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+use std::path::Path;
+use std::process::{self, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::io_source::IoSource;
+use crate::sys::unix::pipe::{self, Receiver, Sender};
+use crate::{event, Interest, Registry, Token};
+
+/// A process builder, wrapping [`std::process::Command`], that spawns a
+/// [`Child`] with non-blocking pipes for its standard streams and an event
+/// source for its exit.
+///
+/// By default all three standard streams of the child are connected to
+/// non-blocking pipes, use [`stdin`], [`stdout`] and [`stderr`] to change
+/// that.
+///
+/// [`stdin`]: Command::stdin
+/// [`stdout`]: Command::stdout
+/// [`stderr`]: Command::stderr
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{self, Read};
+///
+/// use mio::unix::process::Command;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// const STDOUT: Token = Token(0);
+/// const EXIT: Token = Token(1);
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut child = Command::new("echo").arg("Hello world").spawn()?;
+/// let mut stdout = child.take_stdout().unwrap();
+/// poll.registry().register(&mut stdout, STDOUT, Interest::READABLE)?;
+/// poll.registry().register(&mut child, EXIT, Interest::READABLE)?;
+///
+/// let mut output = Vec::new();
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     for event in events.iter() {
+///         match event.token() {
+///             STDOUT => loop {
+///                 let mut buf = [0; 64];
+///                 match stdout.read(&mut buf) {
+///                     Ok(0) => break,
+///                     Ok(n) => output.extend_from_slice(&buf[..n]),
+///                     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///                     Err(err) => return Err(err.into()),
+///                 }
+///             },
+///             EXIT => if let Some(status) = child.try_wait()? {
+///                 assert!(status.success());
+///                 // The exit can be reported before all output is read.
+///                 stdout.set_nonblocking(false)?;
+///                 stdout.read_to_end(&mut output)?;
+///                 assert_eq!(output, b"Hello world\n");
+///                 return Ok(());
+///             },
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Command {
+    inner: process::Command,
+}
+
+impl Command {
+    /// Create a new `Command` for launching `program`, see
+    /// [`std::process::Command::new`].
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Command {
+        Command::from(process::Command::new(program))
+    }
+
+    /// Adds an argument to pass to the program.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Command {
+        self.inner.arg(arg);
+        self
+    }
+
+    /// Adds multiple arguments to pass to the program.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner.args(args);
+        self
+    }
+
+    /// Inserts or updates an environment variable.
+    pub fn env<K, V>(&mut self, key: K, value: V) -> &mut Command
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.inner.env(key, value);
+        self
+    }
+
+    /// Removes an environment variable.
+    pub fn env_remove<K: AsRef<OsStr>>(&mut self, key: K) -> &mut Command {
+        self.inner.env_remove(key);
+        self
+    }
+
+    /// Clears the entire environment.
+    pub fn env_clear(&mut self) -> &mut Command {
+        self.inner.env_clear();
+        self
+    }
+
+    /// Sets the working directory of the child.
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Command {
+        self.inner.current_dir(dir);
+        self
+    }
+
+    /// Configuration for the standard input, defaults to a pipe.
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stdin(cfg);
+        self
+    }
+
+    /// Configuration for the standard output, defaults to a pipe.
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stdout(cfg);
+        self
+    }
+
+    /// Configuration for the standard error, defaults to a pipe.
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stderr(cfg);
+        self
+    }
+
+    /// Spawn the process.
+    pub fn spawn(&mut self) -> io::Result<Child> {
+        let mut child = self.inner.spawn()?;
+        let stdin = child.stdin.take().map(Sender::from);
+        let stdout = child.stdout.take().map(Receiver::from);
+        let stderr = child.stderr.take().map(Receiver::from);
+        let exit = (|| {
+            if let Some(stdin) = &stdin {
+                stdin.set_nonblocking(true)?;
+            }
+            if let Some(stdout) = &stdout {
+                stdout.set_nonblocking(true)?;
+            }
+            if let Some(stderr) = &stderr {
+                stderr.set_nonblocking(true)?;
+            }
+            ExitNotifier::new(child.id())
+        })();
+        let exit = match exit {
+            Ok(exit) => exit,
+            Err(err) => {
+                // Don't leave behind a process the caller can't wait for.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err);
+            }
+        };
+        Ok(Child {
+            inner: child,
+            stdin,
+            stdout,
+            stderr,
+            exit,
+        })
+    }
+}
+
+/// Creates a `Command` with all three standard streams set to pipes.
+///
+/// This overrides any standard stream configuration already set on the
+/// [`std::process::Command`], use [`Command::stdin`], [`Command::stdout`] and
+/// [`Command::stderr`] after the conversion to change it.
+impl From<process::Command> for Command {
+    fn from(mut command: process::Command) -> Command {
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Command { inner: command }
+    }
+}
+
+/// A child process spawned by [`Command::spawn`].
+///
+/// # Events
+///
+/// Registering the `Child` with [`READABLE`] interest returns a [readable
+/// event] once the process exited, after which [`try_wait`] returns its exit
+/// status.
+///
+/// On Linux this uses a pidfd, see [`pidfd_open(2)`], and on the BSDs and
+/// Apple platforms a kqueue with an `EVFILT_PROC` filter, see [`kqueue(2)`].
+/// On other platforms, or older Linux kernels, a helper thread is used that
+/// waits for the process to exit (without reaping it). In that case
+/// [`try_wait`] doesn't reap the process before the helper thread saw it
+/// exit, as the process identifier could otherwise be reused. In no case is a
+/// signal handler for `SIGCHLD` installed.
+///
+/// [`READABLE`]: Interest::READABLE
+/// [readable event]: event::Event::is_readable
+/// [`try_wait`]: Child::try_wait
+/// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
+/// [`kqueue(2)`]: https://man.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+///
+/// # Notes
+///
+/// Just like [`std::process::Child`] the process is not killed, nor waited
+/// for, when the `Child` is dropped.
+#[derive(Debug)]
+pub struct Child {
+    inner: process::Child,
+    stdin: Option<Sender>,
+    stdout: Option<Receiver>,
+    stderr: Option<Receiver>,
+    exit: ExitNotifier,
+}
+
+impl Child {
+    /// Returns the process identifier of the child.
+    pub fn id(&self) -> u32 {
+        self.inner.id()
+    }
+
+    /// Take the non-blocking pipe connected to the standard input of the
+    /// child, if any.
+    ///
+    /// Dropping the pipe closes the standard input of the child.
+    pub fn take_stdin(&mut self) -> Option<Sender> {
+        self.stdin.take()
+    }
+
+    /// Take the non-blocking pipe connected to the standard output of the
+    /// child, if any.
+    pub fn take_stdout(&mut self) -> Option<Receiver> {
+        self.stdout.take()
+    }
+
+    /// Take the non-blocking pipe connected to the standard error of the
+    /// child, if any.
+    pub fn take_stderr(&mut self) -> Option<Receiver> {
+        self.stderr.take()
+    }
+
+    /// Returns the exit status of the child if it exited, without blocking.
+    /// See [`std::process::Child::try_wait`].
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        if !self.exit.has_exited() {
+            return Ok(None);
+        }
+        self.inner.try_wait()
+    }
+
+    /// Wait for the child to exit, blocking the current thread. Closes the
+    /// standard input of the child, if not taken, before waiting. See
+    /// [`std::process::Child::wait`].
+    pub fn wait(&mut self) -> io::Result<ExitStatus> {
+        drop(self.stdin.take());
+        self.exit.wait();
+        self.inner.wait()
+    }
+
+    /// Sends `SIGKILL` to the child, see [`std::process::Child::kill`].
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.inner.kill()
+    }
+}
+
+impl event::Source for Child {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.exit.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.exit.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.exit.deregister(registry)
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.exit.pause(registry, token)
+    }
+}
+
+/// Becomes readable once the process exits.
+#[derive(Debug)]
+enum ExitNotifier {
+    /// Process file descriptor (Linux) or kqueue watching the process (BSDs
+    /// and Apple platforms), readable once the process exits.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "watchos",
+    ))]
+    Fd(IoSource<File>),
+    /// Receiving end of a pipe, which a helper thread writes to once the
+    /// process exits.
+    Thread {
+        receiver: Receiver,
+        /// Set by the helper thread before writing to the pipe.
+        exited: Arc<AtomicBool>,
+        /// `None` once joined.
+        thread: Option<thread::JoinHandle<()>>,
+    },
+}
+
+impl ExitNotifier {
+    fn new(pid: u32) -> io::Result<ExitNotifier> {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        match syscall!(syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0)) {
+            // SAFETY: `pidfd_open(2)` ensures the fd is valid.
+            Ok(fd) => {
+                let file = unsafe { File::from_raw_fd(fd as libc::c_int) };
+                return Ok(ExitNotifier::Fd(IoSource::new(file)));
+            }
+            // Linux 5.3 or later is required, or it may be blocked by seccomp.
+            Err(ref err)
+                if matches!(err.raw_os_error(), Some(libc::ENOSYS) | Some(libc::EPERM)) => {}
+            Err(err) => return Err(err),
+        }
+
+        #[cfg(any(
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "watchos",
+        ))]
+        match crate::sys::unix::selector::new_exit_kqueue(pid) {
+            Ok(kq) => return Ok(ExitNotifier::Fd(IoSource::new(kq))),
+            // Some platforms don't allow watching a process that already
+            // exited, the helper thread handles that.
+            Err(ref err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+            Err(err) => return Err(err),
+        }
+
+        let (sender, receiver) = pipe::new()?;
+        // SAFETY: `into_raw_fd` ensures the fd is valid.
+        let mut sender = unsafe { File::from_raw_fd(sender.into_raw_fd()) };
+        let exited = Arc::new(AtomicBool::new(false));
+        let thread_exited = exited.clone();
+        let thread = thread::Builder::new()
+            .name("mio-child-exit".to_owned())
+            .spawn(move || {
+                if let Err(err) = wait_for_exit(pid) {
+                    error!("error waiting for child process: {}", err);
+                }
+                thread_exited.store(true, Ordering::Release);
+                if let Err(err) = sender.write_all(&[1]) {
+                    error!("error notifying child process exit: {}", err);
+                }
+            })?;
+        Ok(ExitNotifier::Thread {
+            receiver,
+            exited,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns false if the helper thread, if any, didn't yet see the process
+    /// exit. The process must not be reaped before that, otherwise its
+    /// process identifier could be reused by the time the thread waits for
+    /// it.
+    fn has_exited(&self) -> bool {
+        match self {
+            ExitNotifier::Thread { exited, .. } => exited.load(Ordering::Acquire),
+            #[allow(unreachable_patterns)]
+            _ => true,
+        }
+    }
+
+    /// Block until the helper thread, if any, saw the process exit.
+    fn wait(&mut self) {
+        if let ExitNotifier::Thread { thread, .. } = self {
+            if let Some(thread) = thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+/// Wait for the process `pid` to exit, without reaping it.
+fn wait_for_exit(pid: u32) -> io::Result<()> {
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    loop {
+        match syscall!(waitid(
+            libc::P_PID,
+            pid as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOWAIT
+        )) {
+            Ok(_) => return Ok(()),
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            // Already reaped using `Child::try_wait` or `Child::wait`.
+            Err(ref err) if err.raw_os_error() == Some(libc::ECHILD) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+impl event::Source for ExitNotifier {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))]
+            ExitNotifier::Fd(fd) => fd.register(registry, token, interests),
+            ExitNotifier::Thread { receiver, .. } => receiver.register(registry, token, interests),
+        }
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))]
+            ExitNotifier::Fd(fd) => fd.reregister(registry, token, interests),
+            ExitNotifier::Thread { receiver, .. } => receiver.reregister(registry, token, interests),
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match self {
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))]
+            ExitNotifier::Fd(fd) => fd.deregister(registry),
+            ExitNotifier::Thread { receiver, .. } => receiver.deregister(registry),
+        }
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        match self {
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))]
+            ExitNotifier::Fd(fd) => fd.pause(registry, token),
+            ExitNotifier::Thread { receiver, .. } => receiver.pause(registry, token),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+use std::net::{self, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+use crate::sys::unix::net::new_ip_socket;
+use crate::sys::unix::tcp::{bind, listen, set_reuseport};
+
+/// Create a TCP listener bound to `addr` with `SO_REUSEPORT` set.
+pub(crate) fn tcp_listener(addr: SocketAddr, backlog: u32) -> io::Result<net::TcpListener> {
+    let socket = new_ip_socket(addr, libc::SOCK_STREAM)?;
+    // SAFETY: `new_ip_socket` ensures the fd is valid.
+    let listener = unsafe { net::TcpListener::from_raw_fd(socket) };
+    set_reuseport(&listener, true)?;
+    bind(&listener, addr)?;
+    listen(&listener, backlog)?;
+    Ok(listener)
+}
+
+/// Create a UDP socket bound to `addr` with `SO_REUSEPORT` set.
+pub(crate) fn udp_socket(addr: SocketAddr) -> io::Result<net::UdpSocket> {
+    let socket = new_ip_socket(addr, libc::SOCK_DGRAM)?;
+    // SAFETY: `new_ip_socket` ensures the fd is valid.
+    let socket = unsafe { net::UdpSocket::from_raw_fd(socket) };
+    set_reuseport(&socket, true)?;
+    bind(&socket, addr)?;
+    Ok(socket)
+}
+
+/// Attach a classic BPF program to the reuseport group of `socket` that
+/// selects the socket using the CPU the packet arrived on, modulo
+/// `group_size`.
+///
+/// The value returned by the program is an index into the group, which is the
+/// order in which the sockets joined it (when bound, or for TCP listeners when
+/// listening). So socket `n` receives the traffic of the CPUs for which
+/// `cpu % group_size == n`, the caller must create the sockets in the order it
+/// wants them mapped.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn steer_by_cpu(socket: &impl AsRawFd, group_size: u32) -> io::Result<()> {
+    #[cfg(target_os = "android")]
+    use libc::SO_ATTACH_REUSEPORT_CBPF;
+    #[cfg(target_os = "linux")]
+    use crate::sys::unix::net::SO_ATTACH_REUSEPORT_CBPF;
+
+    let mut filter = [
+        // A = CPU the packet was received on.
+        bpf_stmt(
+            libc::BPF_LD | libc::BPF_W | libc::BPF_ABS,
+            (libc::SKF_AD_OFF + libc::SKF_AD_CPU) as u32,
+        ),
+        // A = A % group_size.
+        bpf_stmt(libc::BPF_ALU | libc::BPF_MOD | libc::BPF_K, group_size),
+        // Return A, the index of the socket in the group.
+        bpf_stmt(libc::BPF_RET | libc::BPF_A, 0),
+    ];
+    let program = libc::sock_fprog {
+        len: filter.len() as libc::c_ushort,
+        filter: filter.as_mut_ptr(),
+    };
+    crate::sys::unix::net::set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        SO_ATTACH_REUSEPORT_CBPF,
+        program,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn bpf_stmt(code: u32, k: u32) -> libc::sock_filter {
+    libc::sock_filter {
+        code: code as u16,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
@@ -1,9 +1,16 @@
 use crate::{Interest, Token};
 
-use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLPRI, EPOLLRDHUP};
-use std::os::unix::io::{AsRawFd, RawFd};
+use libc::{EPOLLET, EPOLLIN, EPOLLONESHOT, EPOLLOUT, EPOLLPRI, EPOLLRDHUP};
+use std::mem::ManuallyDrop;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(target_os = "linux")]
+use std::sync::atomic::AtomicU32;
+#[cfg(any(debug_assertions, target_os = "linux"))]
+use std::sync::atomic::Ordering;
 #[cfg(debug_assertions)]
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
 use std::time::Duration;
 use std::{cmp, i32, io, ptr};
 
@@ -16,6 +23,13 @@ pub struct Selector {
     #[cfg(debug_assertions)]
     id: usize,
     ep: RawFd,
+    /// If true all registrations use `EPOLLONESHOT`, see `SharedPoll`.
+    oneshot: bool,
+    /// Value used for `SO_BUSY_POLL` on registered sockets, zero if disabled
+    /// after being enabled or `BUSY_POLL_UNUSED` if never enabled. Shared
+    /// between all clones of the selector.
+    #[cfg(target_os = "linux")]
+    busy_poll_usecs: Arc<AtomicU32>,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
 }
@@ -56,13 +70,16 @@ impl Selector {
             }
         };
 
-        Ok(Selector {
-            #[cfg(debug_assertions)]
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            ep,
-            #[cfg(debug_assertions)]
-            has_waker: AtomicBool::new(false),
-        })
+        // Safety: we just created `ep`.
+        Ok(unsafe { Selector::from_raw_fd(ep) })
+    }
+
+    /// Create a new selector in which all registrations are one-shot, see
+    /// `SharedPoll`.
+    pub fn new_oneshot() -> io::Result<Selector> {
+        let mut selector = Selector::new()?;
+        selector.oneshot = true;
+        Ok(selector)
     }
 
     pub fn try_clone(&self) -> io::Result<Selector> {
@@ -71,6 +88,9 @@ impl Selector {
             #[cfg(debug_assertions)]
             id: self.id,
             ep,
+            oneshot: self.oneshot,
+            #[cfg(target_os = "linux")]
+            busy_poll_usecs: self.busy_poll_usecs.clone(),
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
         })
@@ -115,7 +135,23 @@ impl Selector {
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
         let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
+            events: interests_to_epoll(interests, self.oneshot),
+            u64: usize::from(token) as u64,
+            #[cfg(target_os = "redox")]
+            _pad: 0,
+        };
+
+        #[cfg(target_os = "linux")]
+        self.set_socket_busy_poll(fd);
+
+        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_ADD, fd, &mut event)).map(|_| ())
+    }
+
+    /// Same as `register`, but never uses one-shot mode. Used by `Waker` as it
+    /// can't be reregistered by the user.
+    pub fn register_waker_fd(&self, fd: RawFd, token: Token) -> io::Result<()> {
+        let mut event = libc::epoll_event {
+            events: interests_to_epoll(Interest::READABLE, false),
             u64: usize::from(token) as u64,
             #[cfg(target_os = "redox")]
             _pad: 0,
@@ -126,12 +162,15 @@ impl Selector {
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
         let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
+            events: interests_to_epoll(interests, self.oneshot),
             u64: usize::from(token) as u64,
             #[cfg(target_os = "redox")]
             _pad: 0,
         };
 
+        #[cfg(target_os = "linux")]
+        self.set_socket_busy_poll(fd);
+
         syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, &mut event)).map(|_| ())
     }
 
@@ -139,12 +178,102 @@ impl Selector {
         syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_DEL, fd, ptr::null_mut())).map(|_| ())
     }
 
+    pub fn pause(&self, fd: RawFd, token: Token) -> io::Result<()> {
+        // Keep the registration, but without any interests. Note that
+        // `EPOLLERR` and `EPOLLHUP` are always reported.
+        let mut event = libc::epoll_event {
+            events: EPOLLET as u32,
+            u64: usize::from(token) as u64,
+            #[cfg(target_os = "redox")]
+            _pad: 0,
+        };
+
+        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, &mut event)).map(|_| ())
+    }
+
     #[cfg(debug_assertions)]
     pub fn register_waker(&self) -> bool {
         self.has_waker.swap(true, Ordering::AcqRel)
     }
+
+    /// Configure the epoll busy-poll parameters (`EPIOCSPARAMS`), setting
+    /// `usecs` to zero disables it. `usecs` must not be larger than
+    /// `i32::MAX`.
+    ///
+    /// If that succeeds this also sets `SO_BUSY_POLL` to `usecs` on all sockets
+    /// registered or reregistered after this call, including resetting it to
+    /// zero once disabled.
+    #[cfg(target_os = "linux")]
+    pub fn set_busy_poll(&self, usecs: u32) -> io::Result<()> {
+        let params = libc::epoll_params {
+            busy_poll_usecs: usecs,
+            busy_poll_budget: if usecs == 0 { 0 } else { BUSY_POLL_BUDGET },
+            prefer_busy_poll: 0,
+            __pad: 0,
+        };
+        syscall!(ioctl(self.ep, libc::EPIOCSPARAMS, &params))?;
+        // Don't touch `SO_BUSY_POLL` if busy-polling was never enabled, the
+        // user may have set it themselves.
+        let _ = self.busy_poll_usecs.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |old| {
+            if usecs == 0 && old == BUSY_POLL_UNUSED {
+                None
+            } else {
+                Some(usecs)
+            }
+        });
+        Ok(())
+    }
+
+    /// Returns true if the kernel busy-polls on this epoll instance.
+    #[cfg(target_os = "linux")]
+    pub fn is_busy_poll(&self) -> bool {
+        let mut params = libc::epoll_params {
+            busy_poll_usecs: 0,
+            busy_poll_budget: 0,
+            prefer_busy_poll: 0,
+            __pad: 0,
+        };
+        match syscall!(ioctl(self.ep, libc::EPIOCGPARAMS, &mut params)) {
+            Ok(_) => params.busy_poll_usecs != 0,
+            // Kernels before v6.9 don't support the ioctl.
+            Err(_) => false,
+        }
+    }
+
+    /// Set `SO_BUSY_POLL` on `fd` if busy-polling is, or was, enabled.
+    #[cfg(target_os = "linux")]
+    fn set_socket_busy_poll(&self, fd: RawFd) {
+        let usecs = self.busy_poll_usecs.load(Ordering::Relaxed);
+        if usecs == BUSY_POLL_UNUSED {
+            return;
+        }
+
+        let value = usecs as libc::c_int;
+        // This fails if `fd` is not a socket, or if we're not allowed to
+        // increase the value, neither of which should fail the registration.
+        if let Err(err) = syscall!(setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_BUSY_POLL,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )) {
+            trace!("unable to set SO_BUSY_POLL on fd={}: {}", fd, err);
+        }
+    }
 }
 
+/// Value of `Selector::busy_poll_usecs` if busy-polling was never enabled, not
+/// a valid value as it's larger than `i32::MAX`.
+#[cfg(target_os = "linux")]
+const BUSY_POLL_UNUSED: u32 = u32::MAX;
+
+/// Maximum number of packets processed per busy-poll iteration, same as the
+/// kernel's default (`BUSY_POLL_BUDGET`). Values above `NAPI_POLL_WEIGHT` (64)
+/// require `CAP_NET_ADMIN`.
+#[cfg(target_os = "linux")]
+const BUSY_POLL_BUDGET: u16 = 8;
+
 cfg_io_source! {
     impl Selector {
         #[cfg(debug_assertions)]
@@ -160,6 +289,32 @@ impl AsRawFd for Selector {
     }
 }
 
+impl FromRawFd for Selector {
+    unsafe fn from_raw_fd(ep: RawFd) -> Selector {
+        Selector {
+            #[cfg(debug_assertions)]
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            ep,
+            oneshot: false,
+            #[cfg(target_os = "linux")]
+            busy_poll_usecs: Arc::new(AtomicU32::new(BUSY_POLL_UNUSED)),
+            #[cfg(debug_assertions)]
+            has_waker: AtomicBool::new(false),
+        }
+    }
+}
+
+impl IntoRawFd for Selector {
+    fn into_raw_fd(self) -> RawFd {
+        // Don't run `Drop`, it would close `ep`.
+        let selector = ManuallyDrop::new(self);
+        // But do drop the other fields, which `ManuallyDrop` leaks.
+        #[cfg(target_os = "linux")]
+        drop(unsafe { ptr::read(&selector.busy_poll_usecs) });
+        selector.ep
+    }
+}
+
 impl Drop for Selector {
     fn drop(&mut self) {
         if let Err(err) = syscall!(close(self.ep)) {
@@ -168,13 +323,27 @@ impl Drop for Selector {
     }
 }
 
-fn interests_to_epoll(interests: Interest) -> u32 {
+fn interests_to_epoll(interests: Interest, oneshot: bool) -> u32 {
     let mut kind = EPOLLET;
 
+    if oneshot {
+        kind |= EPOLLONESHOT;
+    }
+
     if interests.is_readable() {
         kind = kind | EPOLLIN | EPOLLRDHUP;
     }
 
+    // NOTE: `EPOLLRDHUP` is only requested for readable and read closed
+    // interests, so `is_read_closed` never reports it for sources registered
+    // with e.g. only writable interest.
+    if interests.is_read_closed() {
+        kind |= EPOLLRDHUP;
+    }
+
+    // NOTE: `EPOLLERR` and `EPOLLHUP` are always reported, so error-only
+    // interests don't need any flags.
+
     if interests.is_writable() {
         kind |= EPOLLOUT;
     }
@@ -189,6 +358,46 @@ fn interests_to_epoll(interests: Interest) -> u32 {
 pub type Event = libc::epoll_event;
 pub type Events = Vec<Event>;
 
+cfg_os_ext! {
+    use std::fmt;
+
+    /// Platform specific view of an [`Event`], as returned by [`Event::raw`].
+    ///
+    /// On this platform it's an `epoll_event` structure, see [`epoll_wait(2)`].
+    ///
+    /// [`Event`]: crate::event::Event
+    /// [`Event::raw`]: crate::event::Event::raw
+    /// [`epoll_wait(2)`]: https://man7.org/linux/man-pages/man2/epoll_wait.2.html
+    #[repr(transparent)]
+    pub struct RawEvent(Event);
+
+    impl RawEvent {
+        /// Returns the `events` field, e.g. `EPOLLIN | EPOLLRDHUP`.
+        pub fn events(&self) -> u32 {
+            self.0.events
+        }
+
+        /// Returns the `data` field, which holds the [`Token`].
+        pub fn data(&self) -> u64 {
+            self.0.u64
+        }
+
+        pub(crate) fn from_sys_event_ref(sys_event: &Event) -> &RawEvent {
+            unsafe {
+                // This is safe because the memory layout of `RawEvent` is the
+                // same as `Event` due to the `repr(transparent)` attribute.
+                &*(sys_event as *const Event as *const RawEvent)
+            }
+        }
+    }
+
+    impl fmt::Debug for RawEvent {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            event::debug_details(f, &self.0)
+        }
+    }
+}
+
 pub mod event {
     use std::fmt;
 
@@ -215,9 +424,10 @@ pub mod event {
     pub fn is_read_closed(event: &Event) -> bool {
         // Both halves of the socket have closed
         event.events as libc::c_int & libc::EPOLLHUP != 0
-            // Socket has received FIN or called shutdown(SHUT_RD)
-            || (event.events as libc::c_int & libc::EPOLLIN != 0
-                && event.events as libc::c_int & libc::EPOLLRDHUP != 0)
+            // Socket has received FIN or called shutdown(SHUT_RD). This is
+            // reported together with `EPOLLIN`, unless the socket was
+            // registered with only `Interest::READ_CLOSED`.
+            || event.events as libc::c_int & libc::EPOLLRDHUP != 0
     }
 
     pub fn is_write_closed(event: &Event) -> bool {
@@ -273,9 +483,11 @@ pub mod event {
         );
 
         // Can't reference fields in packed structures.
+        let e_events = event.events;
         let e_u64 = event.u64;
         f.debug_struct("epoll_event")
-            .field("events", &EventsDetails(event.events))
+            .field("events", &EventsDetails(e_events))
+            .field("events_bits", &format_args!("{:#x}", e_events))
             .field("u64", &e_u64)
             .finish()
     }
@@ -1,7 +1,7 @@
 use crate::{Interest, Token};
-use std::mem::{self, MaybeUninit};
+use std::mem::{self, ManuallyDrop, MaybeUninit};
 use std::ops::{Deref, DerefMut};
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
@@ -66,6 +66,8 @@ pub struct Selector {
     #[cfg(debug_assertions)]
     id: usize,
     kq: RawFd,
+    /// If true all registrations use `EV_DISPATCH`, see `SharedPoll`.
+    oneshot: bool,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
 }
@@ -73,24 +75,28 @@ pub struct Selector {
 impl Selector {
     pub fn new() -> io::Result<Selector> {
         let kq = syscall!(kqueue())?;
-        let selector = Selector {
-            #[cfg(debug_assertions)]
-            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            kq,
-            #[cfg(debug_assertions)]
-            has_waker: AtomicBool::new(false),
-        };
+        // Safety: we just created `kq`.
+        let selector = unsafe { Selector::from_raw_fd(kq) };
 
         syscall!(fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC))?;
         Ok(selector)
     }
 
+    /// Create a new selector in which all registrations are one-shot, see
+    /// `SharedPoll`.
+    pub fn new_oneshot() -> io::Result<Selector> {
+        let mut selector = Selector::new()?;
+        selector.oneshot = true;
+        Ok(selector)
+    }
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         syscall!(fcntl(self.kq, libc::F_DUPFD_CLOEXEC, super::LOWEST_FD)).map(|kq| Selector {
             // It's the same selector, so we use the same id.
             #[cfg(debug_assertions)]
             id: self.id,
             kq,
+            oneshot: self.oneshot,
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
         })
@@ -127,7 +133,27 @@ impl Selector {
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+        self.register_with_flags(fd, token, interests, self.oneshot)
+    }
+
+    /// Same as `register`, but never uses one-shot mode. Used by `Waker` as it
+    /// can't be reregistered by the user.
+    #[cfg(any(target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"))]
+    pub fn register_waker_fd(&self, fd: RawFd, token: Token) -> io::Result<()> {
+        self.register_with_flags(fd, token, Interest::READABLE, false)
+    }
+
+    fn register_with_flags(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interest,
+        oneshot: bool,
+    ) -> io::Result<()> {
+        let mut flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+        if oneshot {
+            flags |= libc::EV_DISPATCH;
+        }
         // At most we need two changes, but maybe we only need 1.
         let mut changes: [MaybeUninit<libc::kevent>; 2] =
             [MaybeUninit::uninit(), MaybeUninit::uninit()];
@@ -139,8 +165,8 @@ impl Selector {
             n_changes += 1;
         }
 
-        if interests.is_readable() {
-            let kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
+        if needs_read_filter(interests) {
+            let kevent = read_kevent(fd, flags, token, interests);
             changes[n_changes] = MaybeUninit::new(kevent);
             n_changes += 1;
         }
@@ -167,20 +193,27 @@ impl Selector {
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
         let flags = libc::EV_CLEAR | libc::EV_RECEIPT;
+        // The filter is disabled after delivering an event in one-shot mode,
+        // or by `pause`, re-adding it with `EV_ENABLE` rearms it.
+        let add_flags = if self.oneshot {
+            flags | libc::EV_ADD | libc::EV_DISPATCH | libc::EV_ENABLE
+        } else {
+            flags | libc::EV_ADD | libc::EV_ENABLE
+        };
         let write_flags = if interests.is_writable() {
-            flags | libc::EV_ADD
+            add_flags
         } else {
             flags | libc::EV_DELETE
         };
-        let read_flags = if interests.is_readable() {
-            flags | libc::EV_ADD
+        let read_flags = if needs_read_filter(interests) {
+            add_flags
         } else {
             flags | libc::EV_DELETE
         };
 
         let mut changes: [libc::kevent; 2] = [
             kevent!(fd, libc::EVFILT_WRITE, write_flags, token.0),
-            kevent!(fd, libc::EVFILT_READ, read_flags, token.0),
+            read_kevent(fd, read_flags, token, interests),
         ];
 
         // Since there is no way to check with which interests the fd was
@@ -213,6 +246,19 @@ impl Selector {
         kevent_register(self.kq, &mut changes, &[libc::ENOENT as i64])
     }
 
+    pub fn pause(&self, fd: RawFd, token: Token) -> io::Result<()> {
+        let flags = libc::EV_DISABLE | libc::EV_RECEIPT;
+        let mut changes: [libc::kevent; 2] = [
+            kevent!(fd, libc::EVFILT_WRITE, flags, token.0),
+            kevent!(fd, libc::EVFILT_READ, flags, token.0),
+        ];
+
+        // Disable, rather than remove, both filters keeping the registration.
+        // Same as in `deregister` we ignore the ENOENT error for a filter that
+        // wasn't registered.
+        kevent_register(self.kq, &mut changes, &[libc::ENOENT as i64])
+    }
+
     #[cfg(debug_assertions)]
     pub fn register_waker(&self) -> bool {
         self.has_waker.swap(true, Ordering::AcqRel)
@@ -299,6 +345,26 @@ fn kevent_register(
     .and_then(|()| check_errors(changes, ignored_errors))
 }
 
+/// Returns true if `interests` require the `EVFILT_READ` filter.
+fn needs_read_filter(interests: Interest) -> bool {
+    interests.is_readable() || interests.is_read_closed() || interests.is_error()
+}
+
+/// Create a `EVFILT_READ` kevent for `interests`.
+///
+/// kqueue doesn't have a filter for only read closed or errors, so if the
+/// caller isn't interested in readable events we set `NOTE_LOWAT` to a value
+/// larger than any receive buffer. The filter will then only trigger on EOF
+/// (`EV_EOF`) or errors.
+fn read_kevent(fd: RawFd, flags: Flags, token: Token, interests: Interest) -> libc::kevent {
+    let mut kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
+    if !interests.is_readable() {
+        kevent.fflags = libc::NOTE_LOWAT;
+        kevent.data = libc::c_int::MAX as _;
+    }
+    kevent
+}
+
 /// Check all events for possible errors, it returns the first error found.
 fn check_errors(events: &[libc::kevent], ignored_errors: &[i64]) -> io::Result<()> {
     for event in events {
@@ -329,6 +395,27 @@ impl AsRawFd for Selector {
     }
 }
 
+impl FromRawFd for Selector {
+    unsafe fn from_raw_fd(kq: RawFd) -> Selector {
+        Selector {
+            #[cfg(debug_assertions)]
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            kq,
+            oneshot: false,
+            #[cfg(debug_assertions)]
+            has_waker: AtomicBool::new(false),
+        }
+    }
+}
+
+impl IntoRawFd for Selector {
+    fn into_raw_fd(self) -> RawFd {
+        // Don't run `Drop`, it would close `kq`.
+        let selector = ManuallyDrop::new(self);
+        selector.kq
+    }
+}
+
 impl Drop for Selector {
     fn drop(&mut self) {
         if let Err(err) = syscall!(close(self.kq)) {
@@ -338,6 +425,88 @@ impl Drop for Selector {
 }
 
 pub type Event = libc::kevent;
+
+cfg_os_ext! {
+    use std::fmt;
+    use std::fs::File;
+
+    /// Create a new kqueue that becomes readable once the process `pid`
+    /// exits, using `EVFILT_PROC` with `NOTE_EXIT`. Used by `process::Child`.
+    ///
+    /// Returns an `ESRCH` error if the process already exited on platforms
+    /// that don't allow watching zombie processes.
+    pub(crate) fn new_exit_kqueue(pid: u32) -> io::Result<File> {
+        let kq = syscall!(kqueue())?;
+        // Safety: we just created `kq`.
+        let kq = unsafe { File::from_raw_fd(kq) };
+        syscall!(fcntl(kq.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC))?;
+
+        let mut kevent = kevent!(pid, libc::EVFILT_PROC, libc::EV_ADD | libc::EV_RECEIPT, 0);
+        kevent.fflags = libc::NOTE_EXIT;
+        kevent_register(kq.as_raw_fd(), slice::from_mut(&mut kevent), &[])?;
+        Ok(kq)
+    }
+
+    /// Platform specific view of an [`Event`], as returned by [`Event::raw`].
+    ///
+    /// On this platform it's a `kevent` structure, see [`kevent(2)`].
+    ///
+    /// [`Event`]: crate::event::Event
+    /// [`Event::raw`]: crate::event::Event::raw
+    /// [`kevent(2)`]: https://man.freebsd.org/cgi/man.cgi?query=kevent&sektion=2
+    #[repr(transparent)]
+    pub struct RawEvent(Event);
+
+    impl RawEvent {
+        /// Returns the `ident` field, usually the file descriptor.
+        pub fn ident(&self) -> usize {
+            self.0.ident as usize
+        }
+
+        /// Returns the `filter` field, e.g. `EVFILT_READ`.
+        pub fn filter(&self) -> Filter {
+            self.0.filter
+        }
+
+        /// Returns the `flags` field, e.g. `EV_EOF`.
+        pub fn flags(&self) -> Flags {
+            self.0.flags
+        }
+
+        /// Returns the `fflags` field, which holds filter specific flags. For
+        /// example the socket error if `EV_EOF` is set in [`flags`].
+        ///
+        /// [`flags`]: RawEvent::flags
+        pub fn fflags(&self) -> u32 {
+            self.0.fflags
+        }
+
+        /// Returns the `data` field, which holds filter specific data. For
+        /// example for `EVFILT_READ` the number of bytes available to read.
+        pub fn data(&self) -> i64 {
+            self.0.data as i64
+        }
+
+        /// Returns the `udata` field, which holds the [`Token`].
+        pub fn udata(&self) -> usize {
+            self.0.udata as usize
+        }
+
+        pub(crate) fn from_sys_event_ref(sys_event: &Event) -> &RawEvent {
+            unsafe {
+                // This is safe because the memory layout of `RawEvent` is the
+                // same as `Event` due to the `repr(transparent)` attribute.
+                &*(sys_event as *const Event as *const RawEvent)
+            }
+        }
+    }
+
+    impl fmt::Debug for RawEvent {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            event::debug_details(f, &self.0)
+        }
+    }
+}
 pub struct Events(Vec<libc::kevent>);
 
 impl Events {
@@ -829,13 +998,17 @@ pub mod event {
 
         // Can't reference fields in packed structures.
         let ident = event.ident;
+        let flags = event.flags;
+        let fflags = event.fflags;
         let data = event.data;
         let udata = event.udata;
         f.debug_struct("kevent")
             .field("ident", &ident)
             .field("filter", &FilterDetails(event.filter))
-            .field("flags", &FlagsDetails(event.flags))
-            .field("fflags", &FflagsDetails(event.fflags))
+            .field("flags", &FlagsDetails(flags))
+            .field("flags_bits", &format_args!("{:#x}", flags))
+            .field("fflags", &FflagsDetails(fflags))
+            .field("fflags_bits", &format_args!("{:#x}", fflags))
             .field("data", &data)
             .field("udata", &udata)
             .finish()
@@ -14,6 +14,17 @@ mod epoll;
 ))]
 pub(crate) use self::epoll::{event, Event, Events, Selector};
 
+#[cfg(all(
+    feature = "os-ext",
+    any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "redox",
+    )
+))]
+pub use self::epoll::RawEvent;
+
 #[cfg(any(
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -38,6 +49,36 @@ mod kqueue;
 ))]
 pub(crate) use self::kqueue::{event, Event, Events, Selector};
 
+#[cfg(all(
+    feature = "os-ext",
+    any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "watchos",
+    )
+))]
+pub use self::kqueue::RawEvent;
+
+#[cfg(all(
+    feature = "os-ext",
+    any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "watchos",
+    )
+))]
+pub(crate) use self::kqueue::new_exit_kqueue;
+
 /// Lowest file descriptor used in `Selector::try_clone`.
 ///
 /// # Notes
@@ -0,0 +1,86 @@
+//! `sendfile(2)`, shared by `TcpStream` and `UnixStream`.
+
+use std::convert::TryInto;
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+/// Send up to `len` bytes of `file`, starting at `offset`, to `socket`.
+/// Returns the number of bytes sent, zero at the end of the file.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn sendfile(socket: RawFd, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+    // Linux transfers at most this many bytes in a single call.
+    const MAX_LEN: usize = 0x7fff_f000;
+
+    let mut offset = file_offset(offset)?;
+    let len = len.min(MAX_LEN);
+    let n = syscall!(sendfile(socket, file.as_raw_fd(), &mut offset, len))?;
+    Ok(n as usize)
+}
+
+/// Send up to `len` bytes of `file`, starting at `offset`, to `socket`.
+/// Returns the number of bytes sent, zero at the end of the file.
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+pub(crate) fn sendfile(socket: RawFd, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+    // A length of zero means sending until the end of the file.
+    if len == 0 {
+        return Ok(0);
+    }
+
+    let offset = file_offset(offset)?;
+    let mut sent: libc::off_t = 0;
+    let res = syscall!(sendfile(
+        file.as_raw_fd(),
+        socket,
+        offset,
+        len,
+        std::ptr::null_mut(),
+        &mut sent,
+        0,
+    ));
+    partial_result(res, sent)
+}
+
+/// Send up to `len` bytes of `file`, starting at `offset`, to `socket`.
+/// Returns the number of bytes sent, zero at the end of the file.
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+pub(crate) fn sendfile(socket: RawFd, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+    // A length of zero means sending until the end of the file.
+    if len == 0 {
+        return Ok(0);
+    }
+
+    let offset = file_offset(offset)?;
+    let mut sent: libc::off_t = len.try_into().unwrap_or(libc::off_t::MAX);
+    let res = syscall!(sendfile(
+        file.as_raw_fd(),
+        socket,
+        offset,
+        &mut sent,
+        std::ptr::null_mut(),
+        0,
+    ));
+    partial_result(res, sent)
+}
+
+fn file_offset(offset: u64) -> io::Result<libc::off_t> {
+    offset
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file offset too large"))
+}
+
+/// On the BSDs `sendfile(2)` can fail, e.g. with `EAGAIN`, after sending part
+/// of the file, in which case the number of bytes sent is returned.
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn partial_result(res: io::Result<libc::c_int>, sent: libc::off_t) -> io::Result<usize> {
+    match res {
+        Err(_) if sent > 0 => Ok(sent as usize),
+        Err(err) => Err(err),
+        Ok(_) => Ok(sent as usize),
+    }
+}
@@ -86,6 +86,10 @@ use std::os::unix::io::RawFd;
 ///     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
 ///         SourceFd(&self.fd).deregister(registry)
 ///     }
+///
+///     fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+///         SourceFd(&self.fd).pause(registry, token)
+///     }
 /// }
 /// ```
 #[derive(Debug)]
@@ -113,4 +117,8 @@ impl<'a> event::Source for SourceFd<'a> {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         registry.selector().deregister(*self.0)
     }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        registry.selector().pause(*self.0, token)
+    }
 }
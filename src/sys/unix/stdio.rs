@@ -0,0 +1,478 @@
+//! Non-blocking standard streams.
+//!
+//! See the [`stdin`] function for documentation.
+
+use std::fs::File;
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::mem::ManuallyDrop;
+use std::os::unix::io::{FromRawFd, IntoRawFd, RawFd};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::io_source::IoSource;
+use crate::sys::unix::pipe;
+use crate::{event, Interest, Registry, Token};
+
+/// Create a non-blocking handle to the standard input of the process.
+///
+/// Simply setting `O_NONBLOCK` on file descriptor 0 (e.g. to use it with
+/// [`SourceFd`]) changes the open file description, which is shared with the
+/// parent process and any other process using the same input. Those processes
+/// generally don't expect the stream to be non-blocking.
+///
+/// Instead, on Linux and Android, FIFOs (pipes) and terminals are reopened
+/// using `/proc/self/fd/0`, creating a new open file description that can
+/// safely be made non-blocking. Other kinds of files (e.g. regular files,
+/// sockets and `/dev/null`) and other platforms fall back to a helper thread,
+/// which uses blocking reads on the standard input and sends the data over a
+/// pipe.
+///
+/// [`SourceFd`]: crate::unix::SourceFd
+///
+/// # Notes
+///
+/// The helper thread, if used, is started by the first call and shared by all
+/// [`Stdin`] handles, which compete for the same input. It keeps running after
+/// the handles are dropped, until it reaches end of file. It reads input ahead
+/// of the handles, but no more than fits in the pipe buffer (and the thread's
+/// own buffer). Data not yet read from a dropped handle is returned by the
+/// next handle instead.
+///
+/// Reads from the standard input using other means, e.g. [`std::io::stdin`],
+/// are not synchronised with reads from the [`Stdin`] handle.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{self, Read};
+/// use std::time::Duration;
+///
+/// use mio::unix::stdio;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut stdin = stdio::stdin()?;
+/// poll.registry().register(&mut stdin, Token(0), Interest::READABLE)?;
+///
+/// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+/// let mut buf = [0; 1024];
+/// match stdin.read(&mut buf) {
+///     Ok(n) => println!("read {} bytes", n),
+///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+///     Err(err) => return Err(err.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn stdin() -> io::Result<Stdin> {
+    let inner = match reopen(libc::STDIN_FILENO, libc::O_RDONLY)? {
+        Some(file) => Inner::reopened(file),
+        None => {
+            let mut helper = STDIN_HELPER.lock().unwrap();
+            if helper.is_none() {
+                let (sender, receiver) = pipe::new()?;
+                sender.set_nonblocking(false)?;
+                // SAFETY: `into_raw_fd` ensures the fd is valid.
+                let sender = unsafe { File::from_raw_fd(sender.into_raw_fd()) };
+                let receiver = unsafe { File::from_raw_fd(receiver.into_raw_fd()) };
+                // The thread is blocked on reading from the standard input, so
+                // it's never joined.
+                let _ = thread::Builder::new()
+                    .name("mio-stdin".to_owned())
+                    .spawn(move || copy(libc::STDIN_FILENO, sender))?;
+                *helper = Some(Helper::new(receiver));
+            }
+            let helper = helper.as_ref().unwrap();
+            Inner::helper_thread(helper.pipe.try_clone()?, None)
+        }
+    };
+    Ok(Stdin { inner })
+}
+
+/// Create a non-blocking handle to the standard output of the process.
+///
+/// See [`stdin`] for how the standard output is made non-blocking.
+///
+/// # Notes
+///
+/// The helper thread, if used, writes output after it was written to the
+/// [`Stdout`] handle. Dropping the handle doesn't wait for this, the remaining
+/// output is written in the background. Use [`Stdout::wait_written`] to wait
+/// until it's written, e.g. before the process exits.
+pub fn stdout() -> io::Result<Stdout> {
+    writer(libc::STDOUT_FILENO, &STDOUT_HELPER, "mio-stdout").map(|inner| Stdout { inner })
+}
+
+/// Create a non-blocking handle to the standard error of the process.
+///
+/// See [`stdin`] for how the standard error is made non-blocking and
+/// [`stdout`] for notes on the helper thread.
+pub fn stderr() -> io::Result<Stderr> {
+    writer(libc::STDERR_FILENO, &STDERR_HELPER, "mio-stderr").map(|inner| Stderr { inner })
+}
+
+fn writer(fd: RawFd, helper: &Mutex<Option<Helper>>, thread_name: &str) -> io::Result<Inner> {
+    match reopen(fd, libc::O_WRONLY)? {
+        Some(file) => Ok(Inner::reopened(file)),
+        None => {
+            let mut helper = helper.lock().unwrap();
+            if helper.is_none() {
+                let (sender, receiver) = pipe::new()?;
+                receiver.set_nonblocking(false)?;
+                // SAFETY: `into_raw_fd` ensures the fd is valid.
+                let sender = unsafe { File::from_raw_fd(sender.into_raw_fd()) };
+                let receiver = unsafe { File::from_raw_fd(receiver.into_raw_fd()) };
+                let progress = Arc::new(Progress::default());
+                let thread_progress = progress.clone();
+                let _ = thread::Builder::new()
+                    .name(thread_name.to_owned())
+                    .spawn(move || copy_to(receiver, fd, &thread_progress))?;
+                *helper = Some(Helper { pipe: sender, progress });
+            }
+            let helper = helper.as_ref().unwrap();
+            let progress = helper.progress.clone();
+            Ok(Inner::helper_thread(
+                helper.pipe.try_clone()?,
+                Some(progress),
+            ))
+        }
+    }
+}
+
+/// Reopen `fd` as a new open file description, in non-blocking mode. Returns
+/// `None` if that's not possible.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn reopen(fd: RawFd, access_mode: libc::c_int) -> io::Result<Option<File>> {
+    let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+    syscall!(fstat(fd, &mut stat))?;
+    let reopen = match stat.st_mode & libc::S_IFMT {
+        libc::S_IFIFO => true,
+        // Not all character devices can be polled, e.g. `/dev/null`.
+        libc::S_IFCHR => unsafe { libc::isatty(fd) == 1 },
+        // Regular files share their offset with the original description, and
+        // sockets can't be reopened.
+        _ => false,
+    };
+    if !reopen {
+        return Ok(None);
+    }
+
+    let path = format!("/proc/self/fd/{}\0", fd);
+    let flags = access_mode | libc::O_NONBLOCK | libc::O_CLOEXEC | libc::O_NOCTTY;
+    match syscall!(open(path.as_ptr().cast(), flags)) {
+        // SAFETY: `open(2)` ensures the fd is valid.
+        Ok(fd) => Ok(Some(unsafe { File::from_raw_fd(fd) })),
+        // E.g. `/proc` is not mounted.
+        Err(_) => Ok(None),
+    }
+}
+
+/// Other platforms don't support reopening file descriptors, e.g.
+/// `/dev/fd/N` duplicates the descriptor instead.
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn reopen(fd: RawFd, _: libc::c_int) -> io::Result<Option<File>> {
+    // Still ensure `fd` is valid.
+    syscall!(fcntl(fd, libc::F_GETFD))?;
+    Ok(None)
+}
+
+/// Copy data read from `fd` into `dst`, until end of file or an error occurs.
+fn copy(fd: RawFd, mut dst: File) {
+    // SAFETY: `ManuallyDrop` ensures we don't close `fd`.
+    let mut src = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+    if let Err(err) = io::copy(&mut *src, &mut dst) {
+        // The handle being dropped shows up as broken pipe.
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            error!("error copying standard stream: {}", err);
+        }
+    }
+}
+
+/// Copy data read from `src` into `fd`, until end of file or an error occurs.
+fn copy_to(mut src: File, fd: RawFd, progress: &Progress) {
+    // SAFETY: `ManuallyDrop` ensures we don't close `fd`.
+    let mut dst = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+    let mut buf = [0; 8 * 1024];
+    let result = loop {
+        match src.read(&mut buf) {
+            Ok(0) => break Ok(()),
+            Ok(n) => match dst.write_all(&buf[..n]) {
+                Ok(()) => progress.written(n),
+                Err(err) => break Err(err),
+            },
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => break Err(err),
+        }
+    };
+    progress.stopped();
+    if let Err(err) = result {
+        error!("error copying standard stream: {}", err);
+    }
+}
+
+/// Helper threads, started by the first handle that needs one and shared by
+/// all handles to the same stream. They're never stopped, the pipe kept here
+/// ensures they don't see end of file (or a broken pipe).
+static STDIN_HELPER: Mutex<Option<Helper>> = Mutex::new(None);
+static STDOUT_HELPER: Mutex<Option<Helper>> = Mutex::new(None);
+static STDERR_HELPER: Mutex<Option<Helper>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Helper {
+    /// End of the pipe to the helper thread, cloned for every handle.
+    pipe: File,
+    /// Progress of the helper thread, only used by output streams.
+    progress: Arc<Progress>,
+}
+
+impl Helper {
+    fn new(pipe: File) -> Helper {
+        Helper {
+            pipe,
+            progress: Arc::new(Progress::default()),
+        }
+    }
+}
+
+/// Progress of an output helper thread, used to wait for it in
+/// `wait_written`.
+#[derive(Debug, Default)]
+struct Progress {
+    state: Mutex<ProgressState>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    /// Bytes written into the pipe by the handles.
+    sent: u64,
+    /// Bytes written to the stream by the helper thread.
+    written: u64,
+    /// Whether the helper thread stopped, e.g. due to an error.
+    stopped: bool,
+}
+
+impl Progress {
+    fn sent(&self, n: usize) {
+        self.state.lock().unwrap().sent += n as u64;
+    }
+
+    fn written(&self, n: usize) {
+        self.state.lock().unwrap().written += n as u64;
+        self.changed.notify_all();
+    }
+
+    fn stopped(&self) {
+        self.state.lock().unwrap().stopped = true;
+        self.changed.notify_all();
+    }
+
+    /// Wait until all bytes sent so far are written, or `timeout` passes.
+    /// Returns true if all bytes were written.
+    fn wait(&self, timeout: Option<Duration>) -> bool {
+        let state = self.state.lock().unwrap();
+        let sent = state.sent;
+        let pending = |state: &mut ProgressState| state.written < sent && !state.stopped;
+        let state = match timeout {
+            Some(timeout) => self.changed.wait_timeout_while(state, timeout, pending).unwrap().0,
+            None => self.changed.wait_while(state, pending).unwrap(),
+        };
+        state.written >= sent
+    }
+}
+
+/// Shared implementation of the standard stream handles.
+#[derive(Debug)]
+struct Inner {
+    /// Either the reopened stream or the end of the pipe to the helper thread.
+    io: IoSource<File>,
+    uses_helper_thread: bool,
+    /// Progress of the output helper thread, see `wait_written`.
+    progress: Option<Arc<Progress>>,
+}
+
+impl Inner {
+    fn reopened(file: File) -> Inner {
+        Inner {
+            io: IoSource::new(file),
+            uses_helper_thread: false,
+            progress: None,
+        }
+    }
+
+    fn helper_thread(file: File, progress: Option<Arc<Progress>>) -> Inner {
+        Inner {
+            io: IoSource::new(file),
+            uses_helper_thread: true,
+            progress,
+        }
+    }
+
+    /// Record that `n` bytes were written into the pipe to the helper thread.
+    fn sent(&self, n: usize) {
+        if let Some(progress) = &self.progress {
+            progress.sent(n);
+        }
+    }
+}
+
+macro_rules! impl_source {
+    ($type: ty) => {
+        impl $type {
+            /// Returns true if the handle uses a helper thread, false if the
+            /// standard stream was reopened.
+            pub fn uses_helper_thread(&self) -> bool {
+                self.inner.uses_helper_thread
+            }
+        }
+
+        impl event::Source for $type {
+            fn register(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> io::Result<()> {
+                self.inner.io.register(registry, token, interests)
+            }
+
+            fn reregister(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> io::Result<()> {
+                self.inner.io.reregister(registry, token, interests)
+            }
+
+            fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+                self.inner.io.deregister(registry)
+            }
+
+            fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+                self.inner.io.pause(registry, token)
+            }
+        }
+    };
+}
+
+macro_rules! impl_write {
+    ($type: ty) => {
+        impl $type {
+            /// Waits until all output written to the handle so far is written
+            /// to the stream by the helper thread, or until `timeout` passes.
+            /// Returns true if all output was written, false if the timeout
+            /// passed or the helper thread stopped due to an error.
+            ///
+            /// This blocks, e.g. if the reader of the stream is not reading.
+            /// Returns true immediately if no helper thread is used.
+            pub fn wait_written(&self, timeout: Option<Duration>) -> bool {
+                match &self.inner.progress {
+                    Some(progress) => progress.wait(timeout),
+                    None => true,
+                }
+            }
+        }
+
+        impl Write for $type {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                (&*self).write(buf)
+            }
+
+            fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+                (&*self).write_vectored(bufs)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                (&*self).flush()
+            }
+        }
+
+        impl Write for &$type {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let n = self
+                    .inner
+                    .io
+                    .do_io_with(Interest::WRITABLE, |mut file| file.write(buf))?;
+                self.inner.sent(n);
+                Ok(n)
+            }
+
+            fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+                let n = self
+                    .inner
+                    .io
+                    .do_io_with(Interest::WRITABLE, |mut file| file.write_vectored(bufs))?;
+                self.inner.sent(n);
+                Ok(n)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.inner
+                    .io
+                    .do_io_with(Interest::WRITABLE, |mut file| file.flush())
+            }
+        }
+    };
+}
+
+/// Non-blocking handle to the standard input.
+///
+/// See [`stdin`] for documentation.
+#[derive(Debug)]
+pub struct Stdin {
+    inner: Inner,
+}
+
+impl_source!(Stdin);
+
+impl Read for Stdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+}
+
+impl Read for &Stdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner
+            .io
+            .do_io_with(Interest::READABLE, |mut file| file.read(buf))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .io
+            .do_io_with(Interest::READABLE, |mut file| file.read_vectored(bufs))
+    }
+}
+
+/// Non-blocking handle to the standard output.
+///
+/// See [`stdout`] for documentation.
+#[derive(Debug)]
+pub struct Stdout {
+    inner: Inner,
+}
+
+impl_source!(Stdout);
+impl_write!(Stdout);
+
+/// Non-blocking handle to the standard error.
+///
+/// See [`stderr`] for documentation.
+#[derive(Debug)]
+pub struct Stderr {
+    inner: Inner,
+}
+
+impl_source!(Stderr);
+impl_write!(Stderr);
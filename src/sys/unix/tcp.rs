@@ -3,8 +3,36 @@ use std::io;
 use std::mem::{size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd};
+#[cfg(not(target_os = "openbsd"))]
+use std::time::Duration;
 
-use crate::sys::unix::net::{new_socket, socket_addr, to_socket_addr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::TcpFastOpen;
+#[cfg(not(target_os = "openbsd"))]
+use crate::net::TcpKeepalive;
+cfg_tcp_info! {
+    use crate::net::{TcpInfo, TcpState};
+}
+use crate::sys::unix::net::{
+    new_socket, set_socket_option, socket_addr, socket_option, to_socket_addr,
+};
+
+// Apple platforms name the idle time option differently.
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+use libc::TCP_KEEPALIVE as KEEPALIVE_TIME;
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "openbsd",
+)))]
+use libc::TCP_KEEPIDLE as KEEPALIVE_TIME;
 
 pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     let domain = match address {
@@ -14,13 +42,13 @@ pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     new_socket(domain, libc::SOCK_STREAM)
 }
 
-pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind(socket: &impl AsRawFd, addr: SocketAddr) -> io::Result<()> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
     syscall!(bind(socket.as_raw_fd(), raw_addr.as_ptr(), raw_addr_length))?;
     Ok(())
 }
 
-pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect(socket: &impl AsRawFd, addr: SocketAddr) -> io::Result<()> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
 
     match syscall!(connect(
@@ -33,24 +61,412 @@ pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<(
     }
 }
 
-pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()> {
+/// Start connecting to `addr` using TCP Fast Open, sending (part of) `data`
+/// in the SYN.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data(
+    socket: &impl AsRawFd,
+    addr: SocketAddr,
+    data: &[u8],
+) -> io::Result<TcpFastOpen> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+
+    match syscall!(sendto(
+        socket.as_raw_fd(),
+        data.as_ptr().cast(),
+        data.len(),
+        libc::MSG_FASTOPEN | libc::MSG_NOSIGNAL,
+        raw_addr.as_ptr(),
+        raw_addr_length,
+    )) {
+        Ok(n) => Ok(TcpFastOpen::Sent(n as usize)),
+        // No cookie is cached for `addr`, the kernel sent a SYN without data
+        // (requesting a cookie for the next connection).
+        Err(err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(TcpFastOpen::NoCookie),
+        // Client side TCP Fast Open is disabled (`net.ipv4.tcp_fastopen`).
+        Err(err) if err.raw_os_error() == Some(libc::EOPNOTSUPP) => {
+            connect(socket, addr).map(|()| TcpFastOpen::Disabled)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub(crate) fn listen(socket: &impl AsRawFd, backlog: u32) -> io::Result<()> {
     let backlog = backlog.try_into().unwrap_or(i32::max_value());
     syscall!(listen(socket.as_raw_fd(), backlog))?;
     Ok(())
 }
 
-pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::Result<()> {
-    let val: libc::c_int = i32::from(reuseaddr);
-    syscall!(setsockopt(
+pub(crate) fn set_reuseaddr(socket: &impl AsRawFd, reuseaddr: bool) -> io::Result<()> {
+    set_socket_option(
         socket.as_raw_fd(),
         libc::SOL_SOCKET,
         libc::SO_REUSEADDR,
-        &val as *const libc::c_int as *const libc::c_void,
-        size_of::<libc::c_int>() as libc::socklen_t,
+        libc::c_int::from(reuseaddr),
+    )
+}
+
+pub(crate) fn reuseaddr(socket: &impl AsRawFd) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEADDR)?;
+    Ok(val != 0)
+}
+
+#[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+pub(crate) fn set_reuseport(socket: &impl AsRawFd, reuseport: bool) -> io::Result<()> {
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_REUSEPORT,
+        libc::c_int::from(reuseport),
+    )
+}
+
+#[cfg(not(any(target_os = "illumos", target_os = "solaris")))]
+pub(crate) fn reuseport(socket: &impl AsRawFd) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+    Ok(val != 0)
+}
+
+pub(crate) fn set_send_buffer_size(socket: &impl AsRawFd, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF, size)
+}
+
+pub(crate) fn send_buffer_size(socket: &impl AsRawFd) -> io::Result<u32> {
+    let size: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+    Ok(size as u32)
+}
+
+pub(crate) fn set_recv_buffer_size(socket: &impl AsRawFd, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF, size)
+}
+
+pub(crate) fn recv_buffer_size(socket: &impl AsRawFd) -> io::Result<u32> {
+    let size: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+    Ok(size as u32)
+}
+
+pub(crate) fn set_only_v6(socket: &impl AsRawFd, only_v6: bool) -> io::Result<()> {
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_IPV6,
+        libc::IPV6_V6ONLY,
+        libc::c_int::from(only_v6),
+    )
+}
+
+pub(crate) fn only_v6(socket: &impl AsRawFd) -> io::Result<bool> {
+    let val: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY)?;
+    Ok(val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn incoming_cpu(socket: &impl AsRawFd) -> io::Result<u32> {
+    use crate::sys::unix::net::SO_INCOMING_CPU;
+
+    let cpu: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, SO_INCOMING_CPU)?;
+    Ok(cpu as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen(socket: &impl AsRawFd, queue_len: u32) -> io::Result<()> {
+    let queue_len = queue_len.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_FASTOPEN,
+        queue_len,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fastopen(socket: &impl AsRawFd) -> io::Result<u32> {
+    let queue_len: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_FASTOPEN)?;
+    Ok(queue_len as u32)
+}
+
+pub(crate) fn set_keepalive(socket: &impl AsRawFd, keepalive: bool) -> io::Result<()> {
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        libc::c_int::from(keepalive),
+    )
+}
+
+pub(crate) fn keepalive(socket: &impl AsRawFd) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_KEEPALIVE)?;
+    Ok(val != 0)
+}
+
+#[cfg(not(target_os = "openbsd"))]
+pub(crate) fn set_keepalive_params(
+    socket: &impl AsRawFd,
+    keepalive: TcpKeepalive,
+) -> io::Result<()> {
+    let fd = socket.as_raw_fd();
+    set_keepalive(socket, true)?;
+    if let Some(time) = keepalive.time {
+        set_socket_option(fd, libc::IPPROTO_TCP, KEEPALIVE_TIME, secs(time))?;
+    }
+    if let Some(interval) = keepalive.interval {
+        set_socket_option(fd, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, secs(interval))?;
+    }
+    if let Some(retries) = keepalive.retries {
+        let retries = retries.try_into().unwrap_or(libc::c_int::MAX);
+        set_socket_option(fd, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "openbsd"))]
+pub(crate) fn keepalive_time(socket: &impl AsRawFd) -> io::Result<Duration> {
+    let secs: libc::c_int = socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, KEEPALIVE_TIME)?;
+    Ok(Duration::from_secs(secs as u64))
+}
+
+#[cfg(not(target_os = "openbsd"))]
+pub(crate) fn keepalive_interval(socket: &impl AsRawFd) -> io::Result<Duration> {
+    let secs: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)?;
+    Ok(Duration::from_secs(secs as u64))
+}
+
+#[cfg(not(target_os = "openbsd"))]
+pub(crate) fn keepalive_retries(socket: &impl AsRawFd) -> io::Result<u32> {
+    let retries: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_KEEPCNT)?;
+    Ok(retries as u32)
+}
+
+/// Converts `duration` to whole seconds, as used by the keepalive options.
+#[cfg(not(target_os = "openbsd"))]
+fn secs(duration: Duration) -> libc::c_int {
+    duration.as_secs().try_into().unwrap_or(libc::c_int::MAX)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout(socket: &impl AsRawFd, timeout: Option<Duration>) -> io::Result<()> {
+    // Zero means the system default is used.
+    let millis = timeout.map_or(0, |timeout| {
+        timeout.as_millis().try_into().unwrap_or(libc::c_int::MAX)
+    });
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+        millis,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn user_timeout(socket: &impl AsRawFd) -> io::Result<Option<Duration>> {
+    let millis: libc::c_int = socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+    )?;
+    if millis == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(Duration::from_millis(millis as u64)))
+    }
+}
+
+cfg_sendfile! {
+    pub(crate) fn sendfile(
+        socket: &impl AsRawFd,
+        file: &std::fs::File,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        crate::sys::unix::sendfile::sendfile(socket.as_raw_fd(), file, offset, len)
+    }
+}
+
+pub(crate) fn set_max_segment_size(socket: &impl AsRawFd, mss: u32) -> io::Result<()> {
+    let mss = mss.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_MAXSEG, mss)
+}
+
+pub(crate) fn max_segment_size(socket: &impl AsRawFd) -> io::Result<u32> {
+    let mss: libc::c_int = socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_MAXSEG)?;
+    Ok(mss as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn set_congestion(socket: &impl AsRawFd, name: &str) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        name.as_ptr().cast(),
+        name.len() as libc::socklen_t,
     ))?;
     Ok(())
 }
 
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn congestion(socket: &impl AsRawFd) -> io::Result<String> {
+    // `TCP_CA_NAME_MAX`.
+    let mut name = [0u8; 16];
+    let mut length = name.len() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        name.as_mut_ptr().cast(),
+        &mut length,
+    ))?;
+    let name = &name[..length as usize];
+    let name = name.split(|b| *b == 0).next().unwrap_or(name);
+    String::from_utf8(name.to_vec()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// `struct tcp_info` from `linux/tcp.h`, up to `tcpi_delivery_rate`. Not all
+/// versions of the libc crate (and the kernel) define all fields.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[repr(C)]
+#[derive(Default)]
+struct LinuxTcpInfo {
+    state: u8,
+    ca_state: u8,
+    retransmits: u8,
+    probes: u8,
+    backoff: u8,
+    options: u8,
+    wscale: u8,
+    delivery_rate_app_limited: u8,
+    rto: u32,
+    ato: u32,
+    snd_mss: u32,
+    rcv_mss: u32,
+    unacked: u32,
+    sacked: u32,
+    lost: u32,
+    retrans: u32,
+    fackets: u32,
+    last_data_sent: u32,
+    last_ack_sent: u32,
+    last_data_recv: u32,
+    last_ack_recv: u32,
+    pmtu: u32,
+    rcv_ssthresh: u32,
+    rtt: u32,
+    rttvar: u32,
+    snd_ssthresh: u32,
+    snd_cwnd: u32,
+    advmss: u32,
+    reordering: u32,
+    rcv_rtt: u32,
+    rcv_space: u32,
+    total_retrans: u32,
+    pacing_rate: u64,
+    max_pacing_rate: u64,
+    bytes_acked: u64,
+    bytes_received: u64,
+    segs_out: u32,
+    segs_in: u32,
+    notsent_bytes: u32,
+    min_rtt: u32,
+    data_segs_in: u32,
+    data_segs_out: u32,
+    delivery_rate: u64,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn tcp_info(socket: &impl AsRawFd) -> io::Result<TcpInfo> {
+    let mut info = LinuxTcpInfo::default();
+    let mut length = size_of::<LinuxTcpInfo>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_TCP,
+        libc::TCP_INFO,
+        (&mut info as *mut LinuxTcpInfo).cast(),
+        &mut length,
+    ))?;
+    // Older kernels return a shorter struct.
+    let delivery_rate = if length as usize >= size_of::<LinuxTcpInfo>() {
+        Some(info.delivery_rate)
+    } else {
+        None
+    };
+    Ok(TcpInfo {
+        state: TcpState::from_linux(info.state),
+        rtt: Duration::from_micros(info.rtt.into()),
+        rtt_var: Some(Duration::from_micros(info.rttvar.into())),
+        congestion_window: info.snd_cwnd.saturating_mul(info.snd_mss),
+        max_segment_size: info.snd_mss,
+        retransmits: info.total_retrans,
+        delivery_rate,
+    })
+}
+
+#[cfg(target_os = "freebsd")]
+pub(crate) fn tcp_info(socket: &impl AsRawFd) -> io::Result<TcpInfo> {
+    let info: libc::tcp_info = tcp_info_option(socket, libc::TCP_INFO)?;
+    Ok(TcpInfo {
+        state: TcpState::from_bsd(info.tcpi_state.into()),
+        rtt: Duration::from_micros(info.tcpi_rtt.into()),
+        rtt_var: Some(Duration::from_micros(info.tcpi_rttvar.into())),
+        congestion_window: info.tcpi_snd_cwnd,
+        max_segment_size: info.tcpi_snd_mss,
+        retransmits: info.tcpi_snd_rexmitpack,
+        delivery_rate: None,
+    })
+}
+
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+pub(crate) fn tcp_info(socket: &impl AsRawFd) -> io::Result<TcpInfo> {
+    let info: libc::tcp_connection_info = tcp_info_option(socket, libc::TCP_CONNECTION_INFO)?;
+    let retransmits = match info.tcpi_maxseg {
+        0 => 0,
+        mss => (info.tcpi_txretransmitbytes / u64::from(mss))
+            .try_into()
+            .unwrap_or(u32::MAX),
+    };
+    Ok(TcpInfo {
+        state: TcpState::from_bsd(info.tcpi_state.into()),
+        rtt: Duration::from_millis(info.tcpi_srtt.into()),
+        rtt_var: Some(Duration::from_millis(info.tcpi_rttvar.into())),
+        congestion_window: info.tcpi_snd_cwnd,
+        max_segment_size: info.tcpi_maxseg,
+        retransmits,
+        delivery_rate: None,
+    })
+}
+
+/// Get a TCP information option, which size may differ between OS versions.
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+fn tcp_info_option<T>(socket: &impl AsRawFd, name: libc::c_int) -> io::Result<T> {
+    // SAFETY: the information structs only contain integers, for which zero
+    // is a valid value.
+    let mut info: T = unsafe { std::mem::zeroed() };
+    let mut length = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        name,
+        (&mut info as *mut T).cast(),
+        &mut length,
+    ))?;
+    Ok(info)
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
     let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
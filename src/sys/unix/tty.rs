@@ -0,0 +1,525 @@
+//! Controlling terminal.
+//!
+//! See the [`Tty`] type for documentation.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::mem::MaybeUninit;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, IntoRawFd, RawFd};
+use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::{ptr, thread};
+
+use crate::io_source::IoSource;
+use crate::sys::unix::pipe;
+use crate::{event, Interest, Registry, Token};
+
+/// Non-blocking access to the controlling terminal of the process.
+///
+/// The `Tty` opens `/dev/tty`, creating a new open file description. This
+/// means that putting it in non-blocking mode doesn't affect standard in- and
+/// output, even if those refer to the same terminal.
+///
+/// # Events
+///
+/// Registering the `Tty` with [`READABLE`] interest returns [readable events]
+/// once input is available, [`WRITABLE`] interest returns [writable events]
+/// once output can be written.
+///
+/// Changes to the window size are also reported as readable events, using
+/// the same token, if the `Tty` is registered with `READABLE` interest. After
+/// receiving a readable event [`take_resize`] should be called to check if the
+/// window was resized.
+///
+/// [`READABLE`]: Interest::READABLE
+/// [readable events]: event::Event::is_readable
+/// [`WRITABLE`]: Interest::WRITABLE
+/// [writable events]: event::Event::is_writable
+/// [`take_resize`]: Tty::take_resize
+///
+/// # Notes
+///
+/// Window size changes are detected using the `SIGWINCH` signal. The first
+/// `Tty` created installs a handler for it, which writes to a pipe and then
+/// calls the previously installed handler (if any). A thread named
+/// `mio-sigwinch` reads from the pipe and notifies all `Tty`s. Neither are
+/// ever removed, so other code changing the handler for `SIGWINCH` afterwards
+/// means no resize events are returned.
+///
+/// The terminal settings are restored to the settings at the time the `Tty`
+/// was opened when it's dropped, see [`Tty::set_raw_mode`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{self, Read};
+///
+/// use mio::unix::Tty;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut tty = Tty::open()?;
+/// tty.set_raw_mode()?;
+/// poll.registry().register(&mut tty, Token(0), Interest::READABLE)?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///
+///     if let Some(size) = tty.take_resize()? {
+///         println!("resized to {}x{}\r", size.columns(), size.rows());
+///     }
+///
+///     let mut buf = [0; 64];
+///     match tty.read(&mut buf) {
+///         // Ctrl-C.
+///         Ok(n) if buf[..n].contains(&3) => break,
+///         Ok(n) => println!("read: {:?}\r", &buf[..n]),
+///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Tty {
+    inner: IoSource<File>,
+    /// Receiving end of the pipe notified on window size changes.
+    resize: pipe::Receiver,
+    /// Id of the subscription to window size changes.
+    resize_id: usize,
+    /// True if `resize` is registered, i.e. the `Tty` is registered with
+    /// readable interest.
+    resize_registered: bool,
+    /// Terminal settings at the time the `Tty` was opened.
+    original: libc::termios,
+    raw: bool,
+}
+
+impl Tty {
+    /// Open the controlling terminal of the process in non-blocking mode.
+    pub fn open() -> io::Result<Tty> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK | libc::O_NOCTTY | libc::O_CLOEXEC)
+            .open("/dev/tty")?;
+
+        let mut original = MaybeUninit::uninit();
+        syscall!(tcgetattr(file.as_raw_fd(), original.as_mut_ptr()))?;
+        // SAFETY: `tcgetattr(3)` initialised the settings.
+        let original = unsafe { original.assume_init() };
+
+        let (resize_id, resize) = subscribe_resize()?;
+        Ok(Tty {
+            inner: IoSource::new(file),
+            resize,
+            resize_id,
+            resize_registered: false,
+            original,
+            raw: false,
+        })
+    }
+
+    /// Put the terminal in raw mode.
+    ///
+    /// In raw mode input is available byte by byte instead of line by line,
+    /// it's not echoed and special characters (such as Ctrl-C) are not
+    /// interpreted. Output is not processed, i.e. `\n` is not translated into
+    /// `\r\n`.
+    ///
+    /// The original settings are restored by [`set_cooked_mode`] or when the
+    /// `Tty` is dropped.
+    ///
+    /// [`set_cooked_mode`]: Tty::set_cooked_mode
+    pub fn set_raw_mode(&mut self) -> io::Result<()> {
+        let mut settings = self.original;
+        make_raw(&mut settings);
+        self.set_settings(&settings)?;
+        self.raw = true;
+        Ok(())
+    }
+
+    /// Restore the terminal settings at the time the `Tty` was opened.
+    pub fn set_cooked_mode(&mut self) -> io::Result<()> {
+        let original = self.original;
+        self.set_settings(&original)?;
+        self.raw = false;
+        Ok(())
+    }
+
+    /// Returns true if the terminal was put in raw mode using
+    /// [`Tty::set_raw_mode`].
+    pub fn is_raw_mode(&self) -> bool {
+        self.raw
+    }
+
+    fn set_settings(&self, settings: &libc::termios) -> io::Result<()> {
+        syscall!(tcsetattr(self.inner.as_raw_fd(), libc::TCSADRAIN, settings)).map(|_| ())
+    }
+
+    /// Returns the current size of the terminal window.
+    pub fn window_size(&self) -> io::Result<WindowSize> {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        syscall!(ioctl(self.inner.as_raw_fd(), libc::TIOCGWINSZ, &mut size))?;
+        Ok(WindowSize {
+            rows: size.ws_row,
+            columns: size.ws_col,
+        })
+    }
+
+    /// Check if the terminal window was resized since the last call.
+    ///
+    /// Returns the current size of the window if it was resized, `None`
+    /// otherwise. Multiple resizes between calls are coalesced.
+    pub fn take_resize(&self) -> io::Result<Option<WindowSize>> {
+        let mut resized = false;
+        let mut buf = [0; 16];
+        loop {
+            match (&self.resize).read(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => resized = true,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        if resized {
+            self.window_size().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl event::Source for Tty {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)?;
+        if interests.is_readable() {
+            self.resize.register(registry, token, Interest::READABLE)?;
+            self.resize_registered = true;
+        }
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)?;
+        match (self.resize_registered, interests.is_readable()) {
+            (true, true) => self.resize.reregister(registry, token, Interest::READABLE)?,
+            (false, true) => self.resize.register(registry, token, Interest::READABLE)?,
+            (true, false) => self.resize.deregister(registry)?,
+            (false, false) => {}
+        }
+        self.resize_registered = interests.is_readable();
+        Ok(())
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)?;
+        if self.resize_registered {
+            self.resize.deregister(registry)?;
+            self.resize_registered = false;
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        self.inner.pause(registry, token)?;
+        if self.resize_registered {
+            self.resize.pause(registry, token)?;
+        }
+        Ok(())
+    }
+}
+
+impl Read for Tty {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+}
+
+impl Read for &Tty {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner
+            .do_io_with(Interest::READABLE, |mut file| file.read(buf))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io_with(Interest::READABLE, |mut file| file.read_vectored(bufs))
+    }
+}
+
+impl Write for Tty {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+impl Write for &Tty {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut file| file.write(buf))
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut file| file.write_vectored(bufs))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner
+            .do_io_with(Interest::WRITABLE, |mut file| file.flush())
+    }
+}
+
+impl AsRawFd for Tty {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl fmt::Debug for Tty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tty")
+            .field("inner", &self.inner)
+            .field("resize", &self.resize)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+impl Drop for Tty {
+    fn drop(&mut self) {
+        if self.raw {
+            if let Err(err) = self.set_cooked_mode() {
+                error!("error restoring terminal settings: {}", err);
+            }
+        }
+        unsubscribe_resize(self.resize_id);
+    }
+}
+
+/// Size of a terminal window, see [`Tty::window_size`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowSize {
+    rows: u16,
+    columns: u16,
+}
+
+impl WindowSize {
+    /// Returns the number of rows (lines).
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Returns the number of columns (characters per line).
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+}
+
+/// Same settings as `cfmakeraw(3)`, which isn't available on all platforms.
+fn make_raw(settings: &mut libc::termios) {
+    settings.c_iflag &= !(libc::IGNBRK
+        | libc::BRKINT
+        | libc::PARMRK
+        | libc::ISTRIP
+        | libc::INLCR
+        | libc::IGNCR
+        | libc::ICRNL
+        | libc::IXON);
+    settings.c_oflag &= !libc::OPOST;
+    settings.c_lflag &= !(libc::ECHO | libc::ECHONL | libc::ICANON | libc::ISIG | libc::IEXTEN);
+    settings.c_cflag &= !(libc::CSIZE | libc::PARENB);
+    settings.c_cflag |= libc::CS8;
+    settings.c_cc[libc::VMIN] = 1;
+    settings.c_cc[libc::VTIME] = 0;
+}
+
+/// `Tty`s waiting for window size changes.
+static SUBSCRIBERS: Mutex<Subscribers> = Mutex::new(Subscribers {
+    installed: false,
+    next_id: 0,
+    senders: Vec::new(),
+});
+
+struct Subscribers {
+    /// True if the `SIGWINCH` handler and the dispatcher thread are set up.
+    installed: bool,
+    next_id: usize,
+    /// Sending ends of the pipes to notify, with the id of their `Tty`.
+    senders: Vec<(usize, pipe::Sender)>,
+}
+
+/// Write end of the self-pipe the `SIGWINCH` handler writes to, -1 if the
+/// handler is not installed. Once set it's never closed.
+static SIGNAL_PIPE: AtomicI32 = AtomicI32::new(-1);
+/// `sa_sigaction` and `sa_flags` of the previously installed `SIGWINCH`
+/// handler, set before installing ours.
+static PREVIOUS_HANDLER: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+static PREVIOUS_FLAGS: AtomicI32 = AtomicI32::new(0);
+
+/// Subscribe to window size changes, returning the id of the subscription and
+/// a pipe that becomes readable after a change.
+fn subscribe_resize() -> io::Result<(usize, pipe::Receiver)> {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    if !subscribers.installed {
+        // Try again on the next call if this fails.
+        install_handler()?;
+        subscribers.installed = true;
+    }
+
+    let (sender, receiver) = pipe::new()?;
+    let id = subscribers.next_id;
+    subscribers.next_id += 1;
+    subscribers.senders.push((id, sender));
+    Ok((id, receiver))
+}
+
+fn unsubscribe_resize(id: usize) {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.senders.retain(|(sender_id, _)| *sender_id != id);
+}
+
+/// Install the `SIGWINCH` handler, which writes to a self-pipe. A dispatcher
+/// thread reads from the pipe and notifies all subscribers, so that the
+/// handler never has to deal with subscribers coming and going.
+fn install_handler() -> io::Result<()> {
+    let (sender, receiver) = pipe::new()?;
+    receiver.set_nonblocking(false)?;
+    thread::Builder::new()
+        .name("mio-sigwinch".to_owned())
+        .spawn(move || dispatch_resizes(receiver))?;
+    // If anything below fails `sender` is dropped, which stops the thread.
+
+    let mut previous: libc::sigaction = unsafe { std::mem::zeroed() };
+    syscall!(sigaction(libc::SIGWINCH, ptr::null(), &mut previous))?;
+    PREVIOUS_HANDLER.store(previous.sa_sigaction, Ordering::SeqCst);
+    PREVIOUS_FLAGS.store(previous.sa_flags, Ordering::SeqCst);
+
+    let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+    action.sa_sigaction = handle_sigwinch as *const () as libc::sighandler_t;
+    action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+    syscall!(sigemptyset(&mut action.sa_mask))?;
+    SIGNAL_PIPE.store(sender.as_raw_fd(), Ordering::SeqCst);
+    if let Err(err) = syscall!(sigaction(libc::SIGWINCH, &action, ptr::null_mut())) {
+        SIGNAL_PIPE.store(-1, Ordering::SeqCst);
+        return Err(err);
+    }
+    // The handler can use the pipe at any time from now on.
+    let _ = sender.into_raw_fd();
+    Ok(())
+}
+
+fn dispatch_resizes(receiver: pipe::Receiver) {
+    let mut buf = [0; 64];
+    loop {
+        match (&receiver).read(&mut buf) {
+            // Installing the handler failed.
+            Ok(0) => return,
+            Ok(_) => {
+                let subscribers = SUBSCRIBERS.lock().unwrap();
+                for (_, sender) in subscribers.senders.iter() {
+                    // If the pipe is full the `Tty` already has a resize to
+                    // process.
+                    let _ = (&*sender).write(&[1]);
+                }
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                error!("error reading SIGWINCH self-pipe: {}", err);
+                return;
+            }
+        }
+    }
+}
+
+extern "C" fn handle_sigwinch(
+    signal: libc::c_int,
+    info: *mut libc::siginfo_t,
+    context: *mut libc::c_void,
+) {
+    // Only async-signal-safe operations are allowed in here.
+    let errno = errno();
+    let fd = SIGNAL_PIPE.load(Ordering::SeqCst);
+    if fd != -1 {
+        // If the pipe is full the dispatcher thread has a resize to process.
+        let _ = unsafe { libc::write(fd, [1u8].as_ptr().cast(), 1) };
+    }
+    set_errno(errno);
+
+    match PREVIOUS_HANDLER.load(Ordering::SeqCst) {
+        libc::SIG_DFL | libc::SIG_IGN => {}
+        handler if PREVIOUS_FLAGS.load(Ordering::SeqCst) & libc::SA_SIGINFO != 0 => {
+            let handler: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                unsafe { std::mem::transmute(handler) };
+            handler(signal, info, context);
+        }
+        handler => {
+            let handler: extern "C" fn(libc::c_int) = unsafe { std::mem::transmute(handler) };
+            handler(signal);
+        }
+    }
+}
+
+#[cfg(target_os = "illumos")]
+use libc::___errno as errno_location;
+#[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+use libc::__errno as errno_location;
+#[cfg(any(target_os = "linux", target_os = "dragonfly"))]
+use libc::__errno_location as errno_location;
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+use libc::__error as errno_location;
+
+#[cfg(not(target_os = "redox"))]
+fn errno() -> libc::c_int {
+    unsafe { *errno_location() }
+}
+
+#[cfg(not(target_os = "redox"))]
+fn set_errno(errno: libc::c_int) {
+    unsafe { *errno_location() = errno }
+}
+
+// Redox doesn't expose errno, the value is lost if the write fails.
+#[cfg(target_os = "redox")]
+fn errno() -> libc::c_int {
+    0
+}
+
+#[cfg(target_os = "redox")]
+fn set_errno(_: libc::c_int) {}
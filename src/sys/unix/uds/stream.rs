@@ -32,3 +32,14 @@ pub(crate) fn local_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
 pub(crate) fn peer_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
     super::peer_addr(socket.as_raw_fd())
 }
+
+cfg_sendfile! {
+    pub(crate) fn sendfile(
+        socket: &net::UnixStream,
+        file: &std::fs::File,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        crate::sys::unix::sendfile::sendfile(socket.as_raw_fd(), file, offset, len)
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod eventfd {
     use crate::sys::Selector;
-    use crate::{Interest, Token};
+    use crate::Token;
 
     use std::fs::File;
     use std::io::{self, Read, Write};
@@ -23,7 +23,7 @@ mod eventfd {
             let fd = syscall!(eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK))?;
             let file = unsafe { File::from_raw_fd(fd) };
 
-            selector.register(fd, token, Interest::READABLE)?;
+            selector.register_waker_fd(fd, token)?;
             Ok(Waker { fd: file })
         }
 
@@ -114,7 +114,7 @@ pub use self::kqueue::Waker;
 ))]
 mod pipe {
     use crate::sys::unix::Selector;
-    use crate::{Interest, Token};
+    use crate::Token;
 
     use std::fs::File;
     use std::io::{self, Read, Write};
@@ -137,7 +137,7 @@ mod pipe {
             let sender = unsafe { File::from_raw_fd(fds[1]) };
             let receiver = unsafe { File::from_raw_fd(fds[0]) };
 
-            selector.register(fds[0], token, Interest::READABLE)?;
+            selector.register_waker_fd(fds[0], token)?;
             Ok(Waker { sender, receiver })
         }
 
@@ -0,0 +1,94 @@
+use std::io;
+use std::mem::{size_of_val, zeroed};
+use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+
+use crate::net::ZerocopyCompletion;
+use crate::sys::unix::net::{set_socket_option, socket_addr, socket_option, SO_ZEROCOPY};
+
+// Not defined by the libc crate.
+const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
+
+pub(crate) fn set_zerocopy(socket: &impl AsRawFd, zerocopy: bool) -> io::Result<()> {
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        SO_ZEROCOPY,
+        libc::c_int::from(zerocopy),
+    )
+}
+
+pub(crate) fn zerocopy(socket: &impl AsRawFd) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, SO_ZEROCOPY)?;
+    Ok(val != 0)
+}
+
+/// Send `buf` using `MSG_ZEROCOPY`, to `target` if provided.
+pub(crate) fn send(
+    socket: &impl AsRawFd,
+    buf: &[u8],
+    target: Option<SocketAddr>,
+) -> io::Result<usize> {
+    let flags = libc::MSG_ZEROCOPY | libc::MSG_NOSIGNAL;
+    let n = match target {
+        Some(target) => {
+            let (raw_addr, raw_addr_length) = socket_addr(&target);
+            syscall!(sendto(
+                socket.as_raw_fd(),
+                buf.as_ptr().cast(),
+                buf.len(),
+                flags,
+                raw_addr.as_ptr(),
+                raw_addr_length,
+            ))?
+        }
+        None => syscall!(send(
+            socket.as_raw_fd(),
+            buf.as_ptr().cast(),
+            buf.len(),
+            flags,
+        ))?,
+    };
+    Ok(n as usize)
+}
+
+/// Receive a single completion notification from the error queue of
+/// `socket`.
+pub(crate) fn recv_completion(socket: &impl AsRawFd) -> io::Result<ZerocopyCompletion> {
+    // Large enough for a `sock_extended_err` followed by the offender's
+    // address, `u64` is used to properly align the control messages.
+    let mut control = [0u64; 16];
+    let mut msg: libc::msghdr = unsafe { zeroed() };
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = size_of_val(&control) as _;
+    syscall!(recvmsg(socket.as_raw_fd(), &mut msg, libc::MSG_ERRQUEUE))?;
+
+    // SAFETY: `recvmsg` initialised the control messages in `msg`.
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    while !cmsg.is_null() {
+        let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+        if (level == libc::SOL_IP && kind == libc::IP_RECVERR)
+            || (level == libc::SOL_IPV6 && kind == libc::IPV6_RECVERR)
+        {
+            let err: libc::sock_extended_err =
+                unsafe { ptr::read_unaligned(libc::CMSG_DATA(cmsg).cast()) };
+            if err.ee_origin != SO_EE_ORIGIN_ZEROCOPY {
+                // Not a completion, but another error (e.g. ICMP errors with
+                // `IP_RECVERR` set).
+                return Err(io::Error::from_raw_os_error(err.ee_errno as i32));
+            }
+            return Ok(ZerocopyCompletion {
+                start: err.ee_info,
+                end: err.ee_data,
+                copied: err.ee_code & SO_EE_CODE_ZEROCOPY_COPIED != 0,
+            });
+        }
+        cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "no extended error in error queue message",
+    ))
+}
@@ -55,7 +55,8 @@ cfg_io_source! {
     struct InternalState {
         selector: Arc<SelectorInner>,
         token: Token,
-        interests: Interest,
+        /// `None` if paused, see `Registry::pause`.
+        interests: Option<Interest>,
         sock_state: Pin<Arc<Mutex<SockState>>>,
     }
 
@@ -86,11 +87,16 @@ cfg_io_source! {
             let result = f(io);
             if let Err(ref e) = result {
                 if e.kind() == io::ErrorKind::WouldBlock {
-                    self.inner.as_ref().map_or(Ok(()), |state| {
-                        state
-                            .selector
-                            .reregister(state.sock_state.clone(), state.token, state.interests)
-                    })?;
+                    if let Some(state) = self.inner.as_ref() {
+                        // Don't resume paused sockets.
+                        if let Some(interests) = state.interests {
+                            state.selector.reregister(
+                                state.sock_state.clone(),
+                                state.token,
+                                interests,
+                            )?;
+                        }
+                    }
                 }
             }
             result
@@ -128,13 +134,26 @@ cfg_io_source! {
                         .reregister(state.sock_state.clone(), token, interests)
                         .map(|()| {
                             state.token = token;
-                            state.interests = interests;
+                            state.interests = Some(interests);
                         })
                 }
                 None => Err(io::ErrorKind::NotFound.into()),
             }
         }
 
+        pub fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+            match self.inner.as_mut() {
+                Some(state) => registry
+                    .selector()
+                    .pause(state.sock_state.clone(), token)
+                    .map(|()| {
+                        state.token = token;
+                        state.interests = None;
+                    }),
+                None => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
         pub fn deregister(&mut self) -> io::Result<()> {
             match self.inner.as_mut() {
                 Some(state) => {
@@ -331,6 +331,8 @@ struct Io {
     cp: Option<Arc<CompletionPort>>,
     // Token used to identify events
     token: Option<Token>,
+    // Set by `pause`, no events are delivered while paused
+    paused: bool,
     read: State,
     write: State,
     connect_error: Option<io::Error>,
@@ -481,6 +483,7 @@ impl FromRawHandle for NamedPipe {
                 io: Mutex::new(Io {
                     cp: None,
                     token: None,
+                    paused: false,
                     read: State::None,
                     write: State::None,
                     connect_error: None,
@@ -632,6 +635,13 @@ impl Source for NamedPipe {
         io.check_association(registry, true)?;
 
         io.token = Some(token);
+        if io.paused {
+            io.paused = false;
+            // Events for completed reads were dropped while paused.
+            if let State::Ok(..) | State::Err(_) = io.read {
+                io.notify_readable(None);
+            }
+        }
         drop(io);
 
         Inner::post_register(&self.inner, None);
@@ -639,6 +649,16 @@ impl Source for NamedPipe {
         Ok(())
     }
 
+    fn pause(&mut self, registry: &Registry, token: Token) -> io::Result<()> {
+        let mut io = self.inner.io.lock().unwrap();
+
+        io.check_association(registry, true)?;
+
+        io.token = Some(token);
+        io.paused = true;
+        Ok(())
+    }
+
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         let mut io = self.inner.io.lock().unwrap();
 
@@ -652,6 +672,7 @@ impl Source for NamedPipe {
         }
 
         io.token = None;
+        io.paused = false;
         Ok(())
     }
 }
@@ -939,6 +960,9 @@ impl Io {
     }
 
     fn notify_readable(&self, events: Option<&mut Vec<Event>>) {
+        if self.paused {
+            return;
+        }
         if let Some(token) = self.token {
             let mut ev = Event::new(token);
             ev.set_readable();
@@ -952,6 +976,9 @@ impl Io {
     }
 
     fn notify_writable(&self, events: Option<&mut Vec<Event>>) {
+        if self.paused {
+            return;
+        }
         if let Some(token) = self.token {
             let mut ev = Event::new(token);
             ev.set_writable();
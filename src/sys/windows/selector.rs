@@ -403,6 +403,14 @@ cfg_io_source! {
             self.inner.reregister(state, token, interests)
         }
 
+        pub(super) fn pause(
+            &self,
+            state: Pin<Arc<Mutex<SockState>>>,
+            token: Token,
+        ) -> io::Result<()> {
+            self.inner.pause(state, token)
+        }
+
         #[cfg(debug_assertions)]
         pub fn id(&self) -> usize {
             self.id
@@ -564,7 +572,7 @@ cfg_io_source! {
             let state = InternalState {
                 selector: this.clone(),
                 token,
-                interests,
+                interests: Some(interests),
                 sock_state: sock.clone(),
             };
 
@@ -580,10 +588,28 @@ cfg_io_source! {
             state: Pin<Arc<Mutex<SockState>>>,
             token: Token,
             interests: Interest,
+        ) -> io::Result<()> {
+            self.set_event(state, token, interests_to_afd_flags(interests))
+        }
+
+        /// Keep the socket registered, but without any interests.
+        pub(super) fn pause(
+            &self,
+            state: Pin<Arc<Mutex<SockState>>>,
+            token: Token,
+        ) -> io::Result<()> {
+            self.set_event(state, token, 0)
+        }
+
+        fn set_event(
+            &self,
+            state: Pin<Arc<Mutex<SockState>>>,
+            token: Token,
+            flags: u32,
         ) -> io::Result<()> {
             {
                 let event = Event {
-                    flags: interests_to_afd_flags(interests),
+                    flags,
                     data: token.0 as u64,
                 };
 
@@ -747,6 +773,15 @@ cfg_net! {
             flags |= WRITABLE_FLAGS | WRITE_CLOSED_FLAGS | ERROR_FLAGS;
         }
 
+        if interests.is_read_closed() {
+            flags |= READ_CLOSED_FLAGS | ERROR_FLAGS;
+        }
+
+        if interests.is_error() {
+            // A reset connection is reported as `POLL_ABORT`.
+            flags |= ERROR_FLAGS | afd::POLL_ABORT;
+        }
+
         flags
     }
 }
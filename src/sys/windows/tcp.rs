@@ -1,16 +1,23 @@
+use std::convert::TryInto;
 use std::io;
+use std::mem::size_of;
 use std::net::{self, SocketAddr};
 use std::os::windows::io::AsRawSocket;
+use std::time::Duration;
 
-use windows_sys::Win32::Networking::WinSock::{self, SOCKET, SOCKET_ERROR, SOCK_STREAM};
+use windows_sys::Win32::Networking::WinSock::{
+    self, IPPROTO_TCP, SOCKET, SOCKET_ERROR, SOCK_STREAM, SOL_SOCKET, SO_KEEPALIVE, TCP_KEEPCNT,
+    TCP_KEEPIDLE, TCP_KEEPINTVL,
+};
 
+use crate::net::{TcpInfo, TcpKeepalive, TcpState};
 use crate::sys::windows::net::{new_ip_socket, socket_addr};
 
 pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<SOCKET> {
     new_ip_socket(address, SOCK_STREAM)
 }
 
-pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind(socket: &impl AsRawSocket, addr: SocketAddr) -> io::Result<()> {
     use WinSock::bind;
 
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
@@ -26,7 +33,7 @@ pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()
     Ok(())
 }
 
-pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect(socket: &impl AsRawSocket, addr: SocketAddr) -> io::Result<()> {
     use WinSock::connect;
 
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
@@ -46,7 +53,7 @@ pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<(
     }
 }
 
-pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()> {
+pub(crate) fn listen(socket: &impl AsRawSocket, backlog: u32) -> io::Result<()> {
     use std::convert::TryInto;
     use WinSock::listen;
 
@@ -59,6 +66,131 @@ pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()>
     Ok(())
 }
 
+pub(crate) fn set_keepalive(socket: &impl AsRawSocket, keepalive: bool) -> io::Result<()> {
+    set_socket_option(socket, SOL_SOCKET, SO_KEEPALIVE, i32::from(keepalive))
+}
+
+pub(crate) fn keepalive(socket: &impl AsRawSocket) -> io::Result<bool> {
+    let val: i32 = socket_option(socket, SOL_SOCKET, SO_KEEPALIVE)?;
+    Ok(val != 0)
+}
+
+// The `TCP_KEEP*` options require Windows 10 version 1709 or later.
+pub(crate) fn set_keepalive_params(
+    socket: &impl AsRawSocket,
+    keepalive: TcpKeepalive,
+) -> io::Result<()> {
+    set_keepalive(socket, true)?;
+    if let Some(time) = keepalive.time {
+        set_socket_option(socket, IPPROTO_TCP, TCP_KEEPIDLE, secs(time))?;
+    }
+    if let Some(interval) = keepalive.interval {
+        set_socket_option(socket, IPPROTO_TCP, TCP_KEEPINTVL, secs(interval))?;
+    }
+    if let Some(retries) = keepalive.retries {
+        set_socket_option(socket, IPPROTO_TCP, TCP_KEEPCNT, retries)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn keepalive_time(socket: &impl AsRawSocket) -> io::Result<Duration> {
+    let secs: u32 = socket_option(socket, IPPROTO_TCP, TCP_KEEPIDLE)?;
+    Ok(Duration::from_secs(secs.into()))
+}
+
+pub(crate) fn keepalive_interval(socket: &impl AsRawSocket) -> io::Result<Duration> {
+    let secs: u32 = socket_option(socket, IPPROTO_TCP, TCP_KEEPINTVL)?;
+    Ok(Duration::from_secs(secs.into()))
+}
+
+pub(crate) fn keepalive_retries(socket: &impl AsRawSocket) -> io::Result<u32> {
+    socket_option(socket, IPPROTO_TCP, TCP_KEEPCNT)
+}
+
+/// Converts `duration` to whole seconds, as used by the keepalive options.
+fn secs(duration: Duration) -> u32 {
+    duration.as_secs().try_into().unwrap_or(u32::MAX)
+}
+
+fn set_socket_option<T>(
+    socket: &impl AsRawSocket,
+    level: i32,
+    name: i32,
+    value: T,
+) -> io::Result<()> {
+    use WinSock::setsockopt;
+
+    syscall!(
+        setsockopt(
+            socket.as_raw_socket() as _,
+            level,
+            name,
+            &value as *const T as *const _,
+            size_of::<T>() as i32,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
+fn socket_option<T: Default>(socket: &impl AsRawSocket, level: i32, name: i32) -> io::Result<T> {
+    use WinSock::getsockopt;
+
+    let mut value = T::default();
+    let mut length = size_of::<T>() as i32;
+    syscall!(
+        getsockopt(
+            socket.as_raw_socket() as _,
+            level,
+            name,
+            &mut value as *mut T as *mut _,
+            &mut length,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(value)
+}
+
+// Requires Windows 10 version 1703 or later.
+pub(crate) fn tcp_info(socket: &impl AsRawSocket) -> io::Result<TcpInfo> {
+    use WinSock::{TCP_INFO_v0, WSAIoctl, SIO_TCP_INFO};
+
+    let version: u32 = 0;
+    // SAFETY: all zeroes is a valid `TCP_INFO_v0`.
+    let mut info: TCP_INFO_v0 = unsafe { std::mem::zeroed() };
+    let mut returned = 0;
+    syscall!(
+        WSAIoctl(
+            socket.as_raw_socket() as _,
+            SIO_TCP_INFO,
+            &version as *const u32 as *const _,
+            size_of::<u32>() as u32,
+            &mut info as *mut TCP_INFO_v0 as *mut _,
+            size_of::<TCP_INFO_v0>() as u32,
+            &mut returned,
+            std::ptr::null_mut(),
+            None,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    let retransmits = match info.Mss {
+        0 => 0,
+        mss => info.BytesRetrans / mss,
+    };
+    Ok(TcpInfo {
+        state: TcpState::from_windows(info.State),
+        rtt: Duration::from_micros(info.RttUs.into()),
+        rtt_var: None,
+        congestion_window: info.Cwnd,
+        max_segment_size: info.Mss,
+        retransmits,
+        delivery_rate: None,
+    })
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     // The non-blocking state of `listener` is inherited. See
     // https://docs.microsoft.com/en-us/windows/win32/api/winsock2/nf-winsock2-accept#remarks.
@@ -41,3 +41,30 @@ fn events_all() {
     events.clear();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn raw_event() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+    waker.wake().expect("unable to wake");
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let event = events.iter().next().expect("no events");
+    let raw = event.raw();
+    #[cfg(any(target_os = "android", target_os = "illumos", target_os = "linux"))]
+    {
+        assert_eq!(raw.data(), WAKE_TOKEN.0 as u64);
+        assert!(raw.events() & libc::EPOLLIN as u32 != 0);
+    }
+    #[cfg(not(any(target_os = "android", target_os = "illumos", target_os = "linux")))]
+    assert_eq!(raw.udata(), WAKE_TOKEN.0);
+
+    // Both should include the raw bits of the flags.
+    let details = format!("{:#?}", event);
+    assert!(details.contains("_bits"), "{}", details);
+    let details = format!("{:?}", raw);
+    assert!(details.contains("_bits"), "{}", details);
+}
@@ -0,0 +1,82 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use mio::net::IcmpSocket;
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, init_with_poll, ExpectEvent};
+
+const ID: Token = Token(0);
+
+#[test]
+#[ignore = "requires `net.ipv4.ping_group_range` to include the group of the user, run by `make test_privileged`"]
+fn ping_dgram_v4() {
+    ping(IcmpSocket::dgram_v4(), Ipv4Addr::LOCALHOST.into(), false);
+}
+
+#[test]
+#[ignore = "requires `net.ipv4.ping_group_range` to include the group of the user, run by `make test_privileged`"]
+fn ping_dgram_v6() {
+    ping(IcmpSocket::dgram_v6(), Ipv6Addr::LOCALHOST.into(), false);
+}
+
+#[test]
+#[ignore = "requires `CAP_NET_RAW`, run by `make test_privileged`"]
+fn ping_raw_v4() {
+    ping(IcmpSocket::raw_v4(), Ipv4Addr::LOCALHOST.into(), true);
+}
+
+#[test]
+#[ignore = "requires `CAP_NET_RAW`, run by `make test_privileged`"]
+fn ping_raw_v6() {
+    ping(IcmpSocket::raw_v6(), Ipv6Addr::LOCALHOST.into(), true);
+}
+
+fn ping(socket: io::Result<IcmpSocket>, target: IpAddr, raw: bool) {
+    let mut socket = socket.unwrap();
+    let (mut poll, mut events) = init_with_poll();
+
+    socket.bind(target).unwrap();
+    assert_eq!(socket.local_addr().unwrap(), target);
+
+    poll.registry()
+        .register(&mut socket, ID, Interest::READABLE)
+        .unwrap();
+    let mut buf = [0; 1024];
+    assert_would_block(socket.recv_echo_reply(&mut buf));
+
+    const PAYLOAD: &[u8] = b"Hello mio";
+    const IDENT: u16 = 0x1234;
+    for seq in 1..=3 {
+        socket
+            .send_echo_request(target, IDENT, seq, PAYLOAD)
+            .unwrap();
+
+        let reply = loop {
+            expect_events(
+                &mut poll,
+                &mut events,
+                vec![ExpectEvent::new(ID, Interest::READABLE)],
+            );
+            match socket.recv_echo_reply(&mut buf) {
+                Ok((reply, addr)) => {
+                    assert_eq!(addr, target);
+                    break reply;
+                }
+                // Raw sockets also receive our own request (and possibly
+                // other ICMP messages), which are skipped.
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        };
+        assert_eq!(reply.seq(), seq);
+        assert_eq!(reply.payload(), PAYLOAD);
+        // Datagram sockets use an identifier picked by the kernel.
+        if raw {
+            assert_eq!(reply.id(), IDENT);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use mio::Interest;
 
+#[cfg(all(feature = "os-poll", feature = "net", not(target_os = "wasi")))]
+mod util;
+
 #[test]
 fn is_tests() {
     assert!(Interest::READABLE.is_readable());
@@ -8,6 +11,13 @@ fn is_tests() {
     assert!(Interest::WRITABLE.is_writable());
     assert!(!Interest::WRITABLE.is_aio());
     assert!(!Interest::WRITABLE.is_lio());
+    assert!(Interest::READ_CLOSED.is_read_closed());
+    assert!(!Interest::READ_CLOSED.is_readable());
+    assert!(!Interest::READ_CLOSED.is_error());
+    assert!(Interest::ERROR.is_error());
+    assert!(!Interest::ERROR.is_readable());
+    assert!(!Interest::ERROR.is_writable());
+    assert!(!Interest::ERROR.is_read_closed());
 }
 
 #[test]
@@ -40,6 +50,12 @@ fn fmt_debug() {
     {
         assert_eq!(format!("{:?}", Interest::LIO), "LIO");
     }
+    assert_eq!(format!("{:?}", Interest::READ_CLOSED), "READ_CLOSED");
+    assert_eq!(format!("{:?}", Interest::ERROR), "ERROR");
+    assert_eq!(
+        format!("{:?}", Interest::READ_CLOSED | Interest::ERROR),
+        "READ_CLOSED | ERROR"
+    );
 }
 
 #[test]
@@ -49,3 +65,125 @@ fn add() {
     assert!(interest.is_readable());
     assert!(interest.is_writable());
 }
+
+#[test]
+#[cfg(all(feature = "os-poll", feature = "net", not(target_os = "wasi")))]
+fn read_closed_only() {
+    use std::io::Write;
+    use std::net::Shutdown;
+
+    use mio::Token;
+    use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent, Readiness};
+
+    const ID: Token = Token(0);
+
+    let (mut poll, mut events) = init_with_poll();
+    let (mut stream, mut peer) = connected_pair();
+
+    poll.registry()
+        .register(&mut stream, ID, Interest::READ_CLOSED)
+        .unwrap();
+
+    // Receiving data should not trigger an event.
+    peer.write_all(b"Hello world!").unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // But the peer closing its writing half should.
+    peer.shutdown(Shutdown::Write).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Readiness::READ_CLOSED)],
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "os-poll",
+    feature = "net",
+    any(target_os = "android", target_os = "linux")
+))]
+fn writable_only_is_not_read_closed() {
+    use std::net::Shutdown;
+    use std::time::Duration;
+
+    use mio::Token;
+    use util::{expect_events, init_with_poll, ExpectEvent, Readiness};
+
+    const ID: Token = Token(0);
+
+    let (mut poll, mut events) = init_with_poll();
+    let (mut stream, peer) = connected_pair();
+
+    poll.registry()
+        .register(&mut stream, ID, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Readiness::WRITABLE)],
+    );
+
+    // The peer closing its writing half may wake up the registration, but
+    // must not be reported as read closed as that wasn't requested.
+    peer.shutdown(Shutdown::Write).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(50)))
+        .unwrap();
+    for event in events.iter() {
+        assert!(!event.is_read_closed(), "unexpected event: {:?}", event);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "os-poll", feature = "net", not(target_os = "wasi")))]
+fn error_only() {
+    use std::io::Write;
+
+    use mio::Token;
+    use util::{
+        expect_events, expect_no_events, init_with_poll, set_linger_zero, ExpectEvent, Readiness,
+    };
+
+    const ID: Token = Token(0);
+
+    let (mut poll, mut events) = init_with_poll();
+    let (mut stream, mut peer) = connected_pair();
+
+    poll.registry()
+        .register(&mut stream, ID, Interest::ERROR)
+        .unwrap();
+
+    // Neither receiving data nor being able to write should trigger an event.
+    peer.write_all(b"Hello world!").unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Resetting the connection should.
+    set_linger_zero(&peer);
+    drop(peer);
+    #[cfg(unix)]
+    let readiness = Readiness::ERROR;
+    // Windows reports a reset connection as closed, not as an error.
+    #[cfg(windows)]
+    let readiness = Readiness::WRITE_CLOSED;
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, readiness)],
+    );
+}
+
+/// Returns a connected (client, server) pair of TCP streams.
+#[cfg(all(feature = "os-poll", feature = "net", not(target_os = "wasi")))]
+fn connected_pair() -> (mio::net::TcpStream, mio::net::TcpStream) {
+    use std::net;
+
+    let listener = net::TcpListener::bind(util::any_local_address()).unwrap();
+    let stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    stream.set_nonblocking(true).unwrap();
+    peer.set_nonblocking(true).unwrap();
+    (
+        mio::net::TcpStream::from_std(stream),
+        mio::net::TcpStream::from_std(peer),
+    )
+}
@@ -0,0 +1,205 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "net"
+))]
+
+use std::convert::TryInto;
+use std::io;
+use std::mem::size_of;
+use std::thread;
+use std::time::Duration;
+
+use mio::net::{NetlinkAddr, NetlinkSocket};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, init_with_poll, ExpectEvent};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const NLMSG_HDR_LEN: usize = size_of::<libc::nlmsghdr>();
+/// Size of `struct ifinfomsg`.
+const IFINFOMSG_LEN: usize = 16;
+
+#[test]
+fn route_dump_links() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = NetlinkSocket::bind(libc::NETLINK_ROUTE, NetlinkAddr::new(0, 0)).unwrap();
+    let local_addr = socket.local_addr().unwrap();
+    assert_ne!(local_addr.port_id(), 0);
+    assert_eq!(local_addr.groups(), 0);
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    let mut buf = vec![0; 32 * 1024];
+    assert_would_block(socket.recv(&mut buf));
+
+    let request = link_message(
+        libc::RTM_GETLINK,
+        (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+        0,
+        0,
+        0,
+    );
+    assert_eq!(socket.send(&request).unwrap(), request.len());
+
+    let mut found_loopback = false;
+    let mut done = false;
+    while !done {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        loop {
+            let (n, addr) = match socket.recv_from(&mut buf) {
+                Ok(res) => res,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {}", err),
+            };
+            // Message from the kernel.
+            assert_eq!(addr.port_id(), 0);
+            for (msg_type, payload) in messages(&buf[..n]) {
+                match msg_type {
+                    libc::RTM_NEWLINK => {
+                        found_loopback |= link_flags(payload) & libc::IFF_LOOPBACK as u32 != 0
+                    }
+                    t if t == libc::NLMSG_DONE as u16 => done = true,
+                    t => panic!("unexpected message type: {}", t),
+                }
+            }
+        }
+    }
+    assert!(found_loopback);
+}
+
+/// Brings the loopback interface up in a new network namespace and expects
+/// `RTM_NEWLINK` notifications.
+#[test]
+#[ignore = "requires `CAP_SYS_ADMIN`, run by `make test_privileged`"]
+fn link_notifications() {
+    // Network namespaces are per thread.
+    thread::spawn(|| {
+        if unsafe { libc::unshare(libc::CLONE_NEWNET) } == -1 {
+            panic!(
+                "can't create network namespace: {}",
+                io::Error::last_os_error()
+            );
+        }
+        link_notifications_in_namespace();
+    })
+    .join()
+    .unwrap();
+}
+
+fn link_notifications_in_namespace() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // One socket subscribed using the bind address, one using `join_group`.
+    let groups = libc::RTMGRP_LINK as u32;
+    let mut socket1 =
+        NetlinkSocket::bind(libc::NETLINK_ROUTE, NetlinkAddr::new(0, groups)).unwrap();
+    assert_eq!(socket1.local_addr().unwrap().groups(), groups);
+    let mut socket2 = NetlinkSocket::bind(libc::NETLINK_ROUTE, NetlinkAddr::new(0, 0)).unwrap();
+    socket2.join_group(libc::RTNLGRP_LINK).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    let lo = unsafe { libc::if_nametoindex(b"lo\0".as_ptr().cast()) };
+    assert_ne!(lo, 0);
+    let request = link_message(
+        libc::RTM_NEWLINK,
+        (libc::NLM_F_REQUEST | libc::NLM_F_ACK) as u16,
+        lo as i32,
+        libc::IFF_UP as u32,
+        libc::IFF_UP as u32,
+    );
+    assert_eq!(socket1.send(&request).unwrap(), request.len());
+
+    let mut acked = false;
+    let mut notified = [false, false];
+    while !acked || notified != [true, true] {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events");
+
+        for (i, socket) in [&socket1, &socket2].iter().enumerate() {
+            let mut buf = vec![0; 8192];
+            loop {
+                let (n, addr) = match socket.recv_from(&mut buf) {
+                    Ok(res) => res,
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error: {}", err),
+                };
+                for (msg_type, payload) in messages(&buf[..n]) {
+                    match msg_type {
+                        libc::RTM_NEWLINK => {
+                            assert_eq!(addr.groups(), groups);
+                            assert!(link_flags(payload) & libc::IFF_UP as u32 != 0);
+                            notified[i] = true;
+                        }
+                        t if t == libc::NLMSG_ERROR as u16 => {
+                            assert_eq!(i, 0, "unexpected ack");
+                            let error = i32::from_ne_bytes(payload[..4].try_into().unwrap());
+                            assert_eq!(error, 0, "request failed");
+                            acked = true;
+                        }
+                        t => panic!("unexpected message type: {}", t),
+                    }
+                }
+            }
+        }
+    }
+
+    socket2.leave_group(libc::RTNLGRP_LINK).unwrap();
+}
+
+/// Create a netlink message with a `struct ifinfomsg` payload.
+fn link_message(msg_type: u16, flags: u16, index: i32, ifi_flags: u32, change: u32) -> Vec<u8> {
+    let len = NLMSG_HDR_LEN + IFINFOMSG_LEN;
+    let mut msg = Vec::with_capacity(len);
+    // `struct nlmsghdr`.
+    msg.extend_from_slice(&(len as u32).to_ne_bytes());
+    msg.extend_from_slice(&msg_type.to_ne_bytes());
+    msg.extend_from_slice(&flags.to_ne_bytes());
+    msg.extend_from_slice(&1u32.to_ne_bytes()); // Sequence number.
+    msg.extend_from_slice(&0u32.to_ne_bytes()); // Port id.
+
+    // `struct ifinfomsg`.
+    msg.push(libc::AF_UNSPEC as u8);
+    msg.push(0);
+    msg.extend_from_slice(&0u16.to_ne_bytes()); // Device type.
+    msg.extend_from_slice(&index.to_ne_bytes());
+    msg.extend_from_slice(&ifi_flags.to_ne_bytes());
+    msg.extend_from_slice(&change.to_ne_bytes());
+    msg
+}
+
+/// Returns the flags of a `struct ifinfomsg`.
+fn link_flags(payload: &[u8]) -> u32 {
+    u32::from_ne_bytes(payload[8..12].try_into().unwrap())
+}
+
+/// Split `buf` into netlink messages, returning the type and payload.
+fn messages(mut buf: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut messages = Vec::new();
+    while buf.len() >= NLMSG_HDR_LEN {
+        let len = u32::from_ne_bytes(buf[..4].try_into().unwrap()) as usize;
+        let msg_type = u16::from_ne_bytes(buf[4..6].try_into().unwrap());
+        messages.push((msg_type, &buf[NLMSG_HDR_LEN..len]));
+        // Messages are aligned to 4 bytes.
+        let aligned = (len + 3) & !3;
+        buf = &buf[aligned.min(buf.len())..];
+    }
+    messages
+}
@@ -2,18 +2,23 @@
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
 use std::net;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 use mio::event::Source;
 use mio::net::{TcpListener, TcpStream, UdpSocket};
+#[cfg(unix)]
+use mio::SharedPoll;
 use mio::{event, Events, Interest, Poll, Registry, Token};
 
 mod util;
 use util::{
-    any_local_address, assert_send, assert_sync, expect_events, init, init_with_poll, ExpectEvent,
+    any_local_address, assert_send, assert_sync, expect_events, expect_no_events, init,
+    init_with_poll, ExpectEvent,
 };
 
 const ID1: Token = Token(1);
@@ -218,6 +223,156 @@ fn registry_behind_arc() {
     handle2.join().unwrap();
 }
 
+#[test]
+fn busy_poll() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    poll.set_busy_poll(Some(Duration::from_millis(10)));
+    // Depends on the kernel version and permissions, but shouldn't panic.
+    let _ = poll.is_kernel_busy_poll();
+
+    // Timeout shorter than the budget.
+    let start = Instant::now();
+    poll.poll(&mut events, Some(Duration::from_millis(1)))
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    // Timeout longer than the budget.
+    poll.poll(&mut events, Some(Duration::from_millis(50)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    poll.set_busy_poll(None);
+    assert!(!poll.is_kernel_busy_poll());
+}
+
+#[test]
+#[cfg(unix)]
+fn shared_poll_one_shot() {
+    init();
+
+    let poll = SharedPoll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let _stream1 = net::TcpStream::connect(addr).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.token() == ID1));
+
+    // The listener is not rearmed, so no more events should be returned.
+    let _stream2 = net::TcpStream::connect(addr).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // After reregistering we should receive an event again.
+    poll.registry()
+        .reregister(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.token() == ID1));
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_from_raw_fd() {
+    use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+
+    let (poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    // The registration should survive transferring the selector.
+    let raw_fd = poll.as_raw_fd();
+    let fd = poll.into_raw_fd();
+    assert_eq!(fd, raw_fd);
+    let mut poll = unsafe { Poll::from_raw_fd(fd) };
+    assert_eq!(poll.as_raw_fd(), raw_fd);
+
+    let _stream1 = net::TcpStream::connect(addr).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let _ = listener.accept().unwrap();
+
+    let mut poll = Poll::from(unsafe { OwnedFd::from_raw_fd(poll.into_raw_fd()) });
+    let _stream2 = net::TcpStream::connect(addr).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn shared_poll_multiple_threads() {
+    init();
+
+    let poll = Arc::new(SharedPoll::new().unwrap());
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    const N_THREADS: usize = 4;
+    let received = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(N_THREADS + 1));
+    let handles: Vec<_> = (0..N_THREADS)
+        .map(|_| {
+            let poll = poll.clone();
+            let received = received.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut events = Events::with_capacity(16);
+                barrier.wait();
+                poll.poll(&mut events, Some(Duration::from_millis(500)))
+                    .unwrap();
+                let n = events.iter().filter(|e| e.token() == ID1).count();
+                received.fetch_add(n, Ordering::SeqCst);
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let _stream = net::TcpStream::connect(addr).unwrap();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // Only a single thread should have received the event.
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+}
+
 /// Call all registration operations, ending with `source` being registered with `token` and `final_interests`.
 pub fn registry_ops_flow(
     registry: &Registry,
@@ -392,6 +547,37 @@ fn reregister_interest_token_usage() {
     );
 }
 
+#[test]
+fn pause_and_resume() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    poll.registry().pause(&mut listener, ID1).unwrap();
+    // Pausing twice is fine.
+    poll.registry().pause(&mut listener, ID1).unwrap();
+
+    // No events should be delivered while paused.
+    let _stream = net::TcpStream::connect(addr).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // After resuming we should receive the pending event.
+    poll.registry()
+        .resume(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    poll.registry().deregister(&mut listener).unwrap();
+}
+
 // This test checks the following register constraint:
 // The event source must **not** have been previously registered with this
 // instance of `Poll`, otherwise the behavior is unspecified.
@@ -598,6 +784,11 @@ fn poll_registration() {
     );
     assert_eq!(source.deregister_count, 0);
 
+    // `TestEventSource` doesn't implement `pause`.
+    let err = registry.pause(&mut source, re_token).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    assert_eq!(source.reregistrations.len(), 1);
+
     registry.deregister(&mut source).unwrap();
     assert_eq!(source.registrations.len(), 1);
     assert_eq!(source.reregistrations.len(), 1);
@@ -0,0 +1,148 @@
+#![cfg(all(
+    unix,
+    not(any(target_os = "illumos", target_os = "solaris")),
+    feature = "os-poll",
+    feature = "net"
+))]
+
+use std::io::{self, Write};
+use std::net;
+
+use mio::net::ReuseportGroup;
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{
+    any_local_address, assert_would_block, expect_events, init, init_with_poll, ExpectEvent,
+};
+
+#[test]
+fn tcp_listeners() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listeners = ReuseportGroup::new(any_local_address(), 4)
+        .backlog(16)
+        .tcp_listeners()
+        .unwrap();
+    assert_eq!(listeners.len(), 4);
+    let addr = listeners[0].local_addr().unwrap();
+    assert_ne!(addr.port(), 0);
+    for (i, listener) in listeners.iter_mut().enumerate() {
+        assert_eq!(listener.local_addr().unwrap(), addr);
+        poll.registry()
+            .register(listener, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    // The connection is accepted by one of the listeners.
+    let mut stream = net::TcpStream::connect(addr).unwrap();
+    poll.poll(&mut events, None).unwrap();
+    let event = events.iter().next().unwrap();
+    let (accepted, peer_addr) = listeners[event.token().0].accept().unwrap();
+    assert_eq!(peer_addr, stream.local_addr().unwrap());
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        let cpu = accepted.incoming_cpu().unwrap();
+        let cpus = std::thread::available_parallelism().unwrap().get();
+        assert!((cpu as usize) < cpus, "invalid cpu: {}", cpu);
+    }
+
+    stream.write_all(b"hello").unwrap();
+    drop(accepted);
+}
+
+#[test]
+fn udp_sockets() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut sockets = ReuseportGroup::new(any_local_address(), 2)
+        .udp_sockets()
+        .unwrap();
+    let addr = sockets[0].local_addr().unwrap();
+    assert_eq!(sockets[1].local_addr().unwrap(), addr);
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    sender.send_to(b"hello", addr).unwrap();
+    poll.poll(&mut events, None).unwrap();
+    let event = events.iter().next().unwrap();
+    let mut buf = [0; 16];
+    let (n, from) = sockets[event.token().0].recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(from, sender.local_addr().unwrap());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn steer_by_cpu() {
+    init();
+
+    // CPU affinity is per thread.
+    std::thread::spawn(|| {
+        // Pin the thread to a single (the last) CPU, ensuring the packets of the
+        // connection over the loopback interface are processed on it.
+        let cpu = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            let size = std::mem::size_of::<libc::cpu_set_t>();
+            assert_eq!(libc::sched_getaffinity(0, size, &mut set), 0);
+            let cpu = (0..libc::CPU_SETSIZE as usize)
+                .rev()
+                .find(|&cpu| libc::CPU_ISSET(cpu, &set))
+                .unwrap();
+            libc::CPU_ZERO(&mut set);
+            libc::CPU_SET(cpu, &mut set);
+            assert_eq!(libc::sched_setaffinity(0, size, &set), 0);
+            cpu
+        };
+
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(8);
+
+        const SIZE: usize = 4;
+        let mut listeners = ReuseportGroup::new(any_local_address(), SIZE)
+            .steer_by_cpu(true)
+            .tcp_listeners()
+            .unwrap();
+        let addr = listeners[0].local_addr().unwrap();
+        for (i, listener) in listeners.iter_mut().enumerate() {
+            poll.registry()
+                .register(listener, Token(i), Interest::READABLE)
+                .unwrap();
+        }
+
+        let expected = cpu % SIZE;
+        for _ in 0..4 {
+            let stream = net::TcpStream::connect(addr).unwrap();
+            expect_events(
+                &mut poll,
+                &mut events,
+                vec![ExpectEvent::new(Token(expected), Interest::READABLE)],
+            );
+            for (i, listener) in listeners.iter().enumerate() {
+                if i != expected {
+                    assert_would_block(listener.accept());
+                }
+            }
+            let (accepted, _) = listeners[expected].accept().unwrap();
+            assert_eq!(accepted.incoming_cpu().unwrap() as usize, cpu);
+            drop(stream);
+        }
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn empty_group() {
+    init();
+
+    let err = ReuseportGroup::new(any_local_address(), 0)
+        .tcp_listeners()
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,196 @@
+#![cfg(all(feature = "os-poll", feature = "net"))]
+
+use std::io::{self, Read, Write};
+use std::net::{self, SocketAddr};
+use std::time::{Duration, Instant};
+
+use mio::net::{TcpConnectError, TcpConnector, TcpStream};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{any_local_address, init, init_with_poll};
+
+const ID: Token = Token(0);
+
+#[test]
+fn connect_first_address() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut connector = TcpConnector::new(vec![addr]).unwrap();
+    assert_eq!(connector.timeout(), None);
+    poll.registry()
+        .register(&mut connector, ID, Interest::WRITABLE)
+        .unwrap();
+
+    let mut stream = connect(&mut poll, &mut events, &mut connector).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+
+    let (mut accepted, _) = listener.accept().unwrap();
+    accepted.write_all(b"hello").unwrap();
+    poll.registry()
+        .register(&mut stream, ID, Interest::READABLE)
+        .unwrap();
+    poll.poll(&mut events, None).unwrap();
+    let mut buf = [0; 5];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
+#[test]
+fn refused_address_falls_back() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let refused = refused_address();
+
+    let mut connector = TcpConnector::new(vec![refused, addr]).unwrap();
+    // Make sure the fallback is caused by the failure, not the delay.
+    connector.set_attempt_delay(Duration::from_secs(60));
+    poll.registry()
+        .register(&mut connector, ID, Interest::WRITABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    let stream = connect(&mut poll, &mut events, &mut connector).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn all_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let addrs = vec![refused_address(), refused_address()];
+    let mut connector = TcpConnector::new(addrs.clone()).unwrap();
+    poll.registry()
+        .register(&mut connector, ID, Interest::WRITABLE)
+        .unwrap();
+
+    let err = connect(&mut poll, &mut events, &mut connector).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    let err = err
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<TcpConnectError>()
+        .unwrap();
+    assert_eq!(err.errors().len(), 2);
+    for ((addr, err), expected) in err.errors().iter().zip(addrs) {
+        assert_eq!(*addr, expected);
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn slow_address_is_raced() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (slow_addr, _slow) = slow_address();
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    const DELAY: Duration = Duration::from_millis(100);
+    let mut connector = TcpConnector::new(vec![slow_addr, addr]).unwrap();
+    connector.set_attempt_delay(DELAY);
+    assert!(connector.timeout().unwrap() <= DELAY);
+    poll.registry()
+        .register(&mut connector, ID, Interest::WRITABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    let stream = connect(&mut poll, &mut events, &mut connector).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    assert!(
+        start.elapsed() >= DELAY / 2,
+        "elapsed: {:?}",
+        start.elapsed()
+    );
+    assert_eq!(connector.timeout(), None);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn failed_attempt_starts_next_attempt() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (slow_addr, _slow) = slow_address();
+    let refused_addr = refused_address();
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // The refused attempt is started after `DELAY`, while the slow attempt is
+    // still in progress. Once it fails the last attempt should start right
+    // away, not after another `DELAY`.
+    const DELAY: Duration = Duration::from_millis(500);
+    let mut connector = TcpConnector::new(vec![slow_addr, refused_addr, addr]).unwrap();
+    connector.set_attempt_delay(DELAY);
+    poll.registry()
+        .register(&mut connector, ID, Interest::WRITABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    let stream = connect(&mut poll, &mut events, &mut connector).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed >= DELAY / 2 && elapsed < DELAY * 2 - DELAY / 4,
+        "elapsed: {:?}",
+        elapsed
+    );
+}
+
+#[test]
+fn no_addresses() {
+    init();
+
+    let err = TcpConnector::new(Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+fn connect(
+    poll: &mut Poll,
+    events: &mut Events,
+    connector: &mut TcpConnector,
+) -> io::Result<TcpStream> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        assert!(Instant::now() < deadline, "connecting took too long");
+        poll.poll(events, connector.timeout().or(Some(Duration::from_secs(1))))
+            .unwrap();
+        match connector.connect(poll.registry()) {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+            res => return res,
+        }
+    }
+}
+
+/// Returns an address to which connecting hangs. The returned listener and
+/// streams must be kept alive for that.
+#[cfg(target_os = "linux")]
+fn slow_address() -> (SocketAddr, (mio::net::TcpListener, Vec<net::TcpStream>)) {
+    // A listener with a full accept queue drops new connection requests,
+    // making connecting to it hang.
+    let socket = mio::net::TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let slow = socket.listen(0).unwrap();
+    let slow_addr = slow.local_addr().unwrap();
+    let mut fillers = Vec::new();
+    loop {
+        let stream = net::TcpStream::connect_timeout(&slow_addr, Duration::from_millis(100));
+        match stream {
+            Ok(stream) => fillers.push(stream),
+            Err(_) => break,
+        }
+    }
+    (slow_addr, (slow, fillers))
+}
+
+/// Returns an address that refuses connections.
+fn refused_address() -> SocketAddr {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    listener.local_addr().unwrap()
+}
@@ -115,6 +115,21 @@ fn get_ttl_without_previous_set() {
     assert!(listener.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_fastopen() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+
+    assert_eq!(listener.fastopen().unwrap(), 0);
+    listener.set_fastopen(16).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 16);
+    listener.set_fastopen(0).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 0);
+    assert!(listener.take_error().unwrap().is_none());
+}
+
 #[cfg(unix)]
 #[test]
 fn raw_fd() {
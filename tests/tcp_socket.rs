@@ -0,0 +1,151 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::io::{self, Read, Write};
+use std::net::{self, SocketAddr};
+
+use mio::net::TcpSocket;
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    any_local_address, any_local_ipv6_address, assert_socket_close_on_exec,
+    assert_socket_non_blocking, expect_events, init, init_with_poll, ExpectEvent,
+};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+const ID3: Token = Token(2);
+
+#[test]
+fn listen_and_connect() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+    socket.set_reuseaddr(true).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let mut listener = socket.listen(16).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Bind the client to a specific local address before connecting.
+    let socket = TcpSocket::new_for_addr(addr).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let mut stream = socket.connect(addr).unwrap();
+    let local_addr = stream.local_addr().unwrap();
+    assert_ne!(local_addr.port(), 0);
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+
+    let (mut accepted, peer_addr) = listener.accept().unwrap();
+    assert_eq!(peer_addr, local_addr);
+
+    poll.registry()
+        .register(&mut accepted, ID3, Interest::READABLE)
+        .unwrap();
+    stream.write_all(b"hello").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+    let mut buf = [0; 5];
+    accepted.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
+#[test]
+fn set_get_options() {
+    init();
+
+    let socket = TcpSocket::new_v4().unwrap();
+
+    socket.set_reuseaddr(true).unwrap();
+    assert!(socket.reuseaddr().unwrap());
+    socket.set_reuseaddr(false).unwrap();
+    assert!(!socket.reuseaddr().unwrap());
+
+    socket.set_reuseport(true).unwrap();
+    assert!(socket.reuseport().unwrap());
+    socket.set_reuseport(false).unwrap();
+    assert!(!socket.reuseport().unwrap());
+
+    // The OS may adjust the buffer size, e.g. Linux doubles it.
+    socket.set_send_buffer_size(16 * 1024).unwrap();
+    assert!(socket.send_buffer_size().unwrap() >= 16 * 1024);
+    socket.set_recv_buffer_size(16 * 1024).unwrap();
+    assert!(socket.recv_buffer_size().unwrap() >= 16 * 1024);
+}
+
+#[test]
+fn only_v6() {
+    init();
+
+    let socket = TcpSocket::new_v6().unwrap();
+    socket.set_only_v6(true).unwrap();
+    assert!(socket.only_v6().unwrap());
+    socket.set_only_v6(false).unwrap();
+    assert!(!socket.only_v6().unwrap());
+
+    socket.bind(any_local_ipv6_address()).unwrap();
+    let listener = socket.listen(1).unwrap();
+    assert!(listener.local_addr().unwrap().is_ipv6());
+}
+
+#[test]
+fn reuseport() {
+    init();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseport(true).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let listener1 = socket.listen(16).unwrap();
+    let addr = listener1.local_addr().unwrap();
+
+    // Without `SO_REUSEPORT` binding to the same address fails.
+    let socket = TcpSocket::new_v4().unwrap();
+    assert!(socket.bind(addr).is_err());
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseport(true).unwrap();
+    socket.bind(addr).unwrap();
+    let listener2 = socket.listen(16).unwrap();
+    assert_eq!(listener2.local_addr().unwrap(), addr);
+}
+
+#[test]
+fn connect_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address that is not accepting connections.
+    let addr: SocketAddr = {
+        let listener = net::TcpListener::bind(any_local_address()).unwrap();
+        listener.local_addr().unwrap()
+    };
+
+    let socket = TcpSocket::new_for_addr(addr).unwrap();
+    let mut stream = socket.connect(addr).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    let err = stream.take_error().unwrap().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
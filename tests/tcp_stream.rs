@@ -9,6 +9,10 @@ use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
+#[cfg(not(target_os = "openbsd"))]
+#[cfg(target_os = "linux")]
+use mio::net::TcpFastOpen;
+use mio::net::TcpKeepalive;
 use mio::net::TcpStream;
 use mio::{Interest, Token};
 
@@ -260,6 +264,427 @@ fn get_nodelay_without_previous_set() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn set_get_keepalive() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    // on Windows: the stream must be connected before setting keepalive, otherwise
+    // it is unspecified behavior, register and expect a WRITABLE here to make sure
+    // the stream is connected
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.keepalive().unwrap());
+    stream.set_keepalive(true).unwrap();
+    assert!(stream.keepalive().unwrap());
+    stream.set_keepalive(false).unwrap();
+    assert!(!stream.keepalive().unwrap());
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn set_get_keepalive_params() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = mio::net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+    let (accepted, _) = listener.accept().unwrap();
+
+    // Both connected and accepted streams can be configured.
+    for (i, stream) in [stream, accepted].iter().enumerate() {
+        let i = i as u64;
+        let keepalive = TcpKeepalive::new()
+            .with_time(Duration::from_secs(60 + i))
+            .with_interval(Duration::from_secs(10 + i))
+            .with_retries(5 + i as u32);
+        stream.set_keepalive_params(keepalive).unwrap();
+        assert!(stream.keepalive().unwrap());
+        assert_eq!(
+            stream.keepalive_time().unwrap(),
+            Duration::from_secs(60 + i)
+        );
+        assert_eq!(
+            stream.keepalive_interval().unwrap(),
+            Duration::from_secs(10 + i)
+        );
+        assert_eq!(stream.keepalive_retries().unwrap(), 5 + i as u32);
+
+        // Parameters not set are left as is.
+        let keepalive = TcpKeepalive::new().with_retries(3);
+        stream.set_keepalive_params(keepalive).unwrap();
+        assert_eq!(
+            stream.keepalive_time().unwrap(),
+            Duration::from_secs(60 + i)
+        );
+        assert_eq!(stream.keepalive_retries().unwrap(), 3);
+        assert!(stream.take_error().unwrap().is_none());
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_user_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = mio::net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let stream = TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (accepted, _) = listener.accept().unwrap();
+
+    for stream in [stream, accepted].iter() {
+        assert_eq!(stream.user_timeout().unwrap(), None);
+        const TIMEOUT: Duration = Duration::from_millis(1500);
+        stream.set_user_timeout(Some(TIMEOUT)).unwrap();
+        assert_eq!(stream.user_timeout().unwrap(), Some(TIMEOUT));
+        stream.set_user_timeout(None).unwrap();
+        assert_eq!(stream.user_timeout().unwrap(), None);
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn connect_with_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // The listener doesn't have Fast Open enabled, so (most likely) nothing is
+    // sent in the SYN and the data has to be written once connected.
+    let (mut stream, fastopen) = TcpStream::connect_with_data(address, DATA1).unwrap();
+    let n = fastopen.sent();
+    assert!(n <= DATA1_LEN);
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    assert_eq!(stream.peer_addr().unwrap(), address);
+    stream.write_all(&DATA1[n..]).unwrap();
+
+    let (mut accepted, _) = listener.accept().unwrap();
+    let mut buf = [0; DATA1_LEN];
+    accepted.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA1);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+#[ignore = "requires `CAP_SYS_ADMIN`, run by `make test_privileged`"]
+fn connect_with_data_in_syn() {
+    // Enabling server side Fast Open requires changing a sysctl, which is done
+    // in a new network namespace, these are per thread.
+    thread::spawn(|| {
+        if unsafe { libc::unshare(libc::CLONE_NEWNET) } == -1 {
+            panic!(
+                "can't create network namespace: {}",
+                io::Error::last_os_error()
+            );
+        }
+        set_loopback_up();
+        connect_with_data_in_namespace();
+    })
+    .join()
+    .unwrap();
+}
+
+#[cfg(target_os = "linux")]
+fn connect_with_data_in_namespace() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = mio::net::TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    listener.set_fastopen(16).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    // With client side Fast Open disabled a normal connect is started.
+    std::fs::write("/proc/sys/net/ipv4/tcp_fastopen", "0").unwrap();
+    let (stream, fastopen) = TcpStream::connect_with_data(address, DATA1).unwrap();
+    assert_eq!(fastopen, TcpFastOpen::Disabled);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    drop(listener.accept().unwrap());
+    drop(stream);
+
+    std::fs::write("/proc/sys/net/ipv4/tcp_fastopen", "3").unwrap();
+    // The first connection falls back to a normal handshake, retrieving a
+    // cookie.
+    let (mut stream, fastopen) = TcpStream::connect_with_data(address, DATA1).unwrap();
+    assert_eq!(fastopen, TcpFastOpen::NoCookie);
+    assert_eq!(fastopen.sent(), 0);
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+    stream.write_all(DATA1).unwrap();
+    let (accepted, _) = listener.accept().unwrap();
+    assert_read_blocking(accepted, DATA1);
+    drop(stream);
+
+    // The second connection sends the data in the SYN using the cookie.
+    let (_stream, fastopen) = TcpStream::connect_with_data(address, DATA2).unwrap();
+    assert_eq!(fastopen, TcpFastOpen::Sent(DATA2_LEN));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (accepted, _) = listener.accept().unwrap();
+    assert_read_blocking(accepted, DATA2);
+}
+
+#[cfg(target_os = "linux")]
+fn assert_read_blocking(stream: TcpStream, expected: &[u8]) {
+    let mut stream = unsafe { net::TcpStream::from_raw_fd(stream.into_raw_fd()) };
+    stream.set_nonblocking(false).unwrap();
+    let mut buf = vec![0; expected.len()];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, expected);
+}
+
+/// Bring up the loopback interface, which is down in a new network namespace.
+#[cfg(target_os = "linux")]
+fn set_loopback_up() {
+    unsafe {
+        let socket = libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0);
+        assert!(socket >= 0);
+        let mut req: libc::ifreq = std::mem::zeroed();
+        for (dst, src) in req.ifr_name.iter_mut().zip(b"lo") {
+            *dst = *src as libc::c_char;
+        }
+        assert_eq!(libc::ioctl(socket, libc::SIOCGIFFLAGS as _, &mut req), 0);
+        req.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+        assert_eq!(libc::ioctl(socket, libc::SIOCSIFFLAGS as _, &req), 0);
+        libc::close(socket);
+    }
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+fn sendfile() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Larger than the socket buffers, so that `sendfile` blocks.
+    let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let path = util::temp_file("tcp_stream_sendfile");
+    std::fs::write(&path, &data).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let mut peer = Some(listener.accept().unwrap().0);
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    // Skip the first bytes to test the offset.
+    const START: usize = 10;
+    let mut offset = START;
+    let mut reader = None;
+    while offset < data.len() {
+        match stream.sendfile(&file, offset as u64, data.len() - offset) {
+            Ok(n) => {
+                assert_ne!(n, 0);
+                offset += n;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                // Only start reading once the buffers are full.
+                if let Some(mut peer) = peer.take() {
+                    reader = Some(thread::spawn(move || {
+                        let mut received = Vec::new();
+                        peer.read_to_end(&mut received).unwrap();
+                        received
+                    }));
+                }
+                expect_events(
+                    &mut poll,
+                    &mut events,
+                    vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+                );
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    // At the end of the file.
+    assert_eq!(stream.sendfile(&file, offset as u64, 100).unwrap(), 0);
+    assert_eq!(stream.sendfile(&file, 0, 0).unwrap(), 0);
+    drop(stream);
+
+    let received = reader.expect("sendfile never blocked").join().unwrap();
+    assert!(received == data[START..], "received different data");
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+    windows,
+))]
+fn tcp_info() {
+    use mio::net::TcpState;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    let (mut peer, _) = listener.accept().unwrap();
+
+    stream.write_all(DATA1).unwrap();
+    let mut buf = [0; DATA1_LEN];
+    peer.read_exact(&mut buf).unwrap();
+
+    let info = stream.tcp_info().unwrap();
+    assert_eq!(info.state(), TcpState::Established);
+    assert!(info.congestion_window() > 0, "{:?}", info);
+    assert!(info.max_segment_size() > 0, "{:?}", info);
+    assert_eq!(info.retransmits(), 0);
+    #[cfg(unix)]
+    assert_eq!(info.max_segment_size(), stream.max_segment_size().unwrap());
+    #[cfg(target_os = "linux")]
+    assert!(info.delivery_rate().is_some());
+
+    // The peer closing the connection changes the state.
+    drop(peer);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(stream.tcp_info().unwrap().state(), TcpState::CloseWait);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+fn set_get_congestion() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // Reno is always available.
+    stream.set_congestion("reno").unwrap();
+    assert_eq!(stream.congestion().unwrap(), "reno");
+    assert!(stream.set_congestion("no_such_algorithm").is_err());
+    assert_eq!(stream.congestion().unwrap(), "reno");
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(unix)]
+fn set_get_max_segment_size() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(stream.max_segment_size().unwrap() > 0);
+    stream.set_max_segment_size(1000).unwrap();
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
 #[test]
 fn shutdown_read() {
     let (mut poll, mut events) = init_with_poll();
@@ -405,6 +830,52 @@ fn shutdown_both() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn readiness() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (handle, addr) = echo_listener(any_local_address(), 1);
+    let mut stream = TcpStream::connect(addr).unwrap();
+    assert_eq!(
+        stream.readiness(),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE.add(Interest::READABLE))
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let mut buf = [0; 16];
+    assert_would_block(stream.read(&mut buf));
+    assert_eq!(stream.readiness(), Some(Interest::WRITABLE));
+
+    checked_write!(stream.write(DATA1));
+
+    // The readiness is set by `Poll::poll`.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(
+        stream.readiness(),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+    expect_read!(stream.peek(&mut buf), DATA1);
+    expect_read!(stream.read(&mut buf), DATA1);
+    assert_would_block(stream.peek(&mut buf));
+    assert_eq!(stream.readiness(), Some(Interest::WRITABLE));
+
+    drop(stream);
+    handle.join().expect("unable to join thread");
+}
+
 #[cfg(unix)]
 #[test]
 fn raw_fd() {
@@ -0,0 +1,170 @@
+#![cfg(all(
+    target_os = "linux",
+    feature = "os-poll",
+    feature = "os-ext",
+    feature = "net"
+))]
+
+use std::fs::OpenOptions;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+
+use mio::unix::aio::Context;
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, init_with_poll, temp_file, ExpectEvent};
+
+const ID: Token = Token(0);
+
+const DATA: &[u8] = b"Hello world!";
+
+#[test]
+fn write_then_read() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("aio_write_then_read");
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+
+    let mut aio = Context::new(8).unwrap();
+    poll.registry()
+        .register(&mut aio, ID, Interest::READABLE)
+        .unwrap();
+
+    let mut completions = Vec::new();
+    assert_would_block(aio.reap(&mut completions));
+
+    unsafe {
+        aio.submit_write(file.as_raw_fd(), DATA.as_ptr(), DATA.len(), 0, 1)
+            .unwrap();
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    assert_eq!(aio.reap(&mut completions).unwrap(), 1);
+    assert_eq!(completions[0].user_data(), 1);
+    assert_eq!(completions[0].result().unwrap(), DATA.len());
+    assert_would_block(aio.reap(&mut completions));
+
+    let mut buf = [0; 64];
+    unsafe {
+        aio.submit_read(file.as_raw_fd(), buf.as_mut_ptr(), buf.len(), 0, 2)
+            .unwrap();
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    completions.clear();
+    assert_eq!(aio.reap(&mut completions).unwrap(), 1);
+    assert_eq!(completions[0].user_data(), 2);
+    let n = completions[0].result().unwrap();
+    assert_eq!(&buf[..n], DATA);
+}
+
+/// Buffer aligned to the page size, as required by `O_DIRECT`.
+#[repr(align(4096))]
+struct AlignedBuf([u8; 4096]);
+
+#[test]
+fn direct_io() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("aio_direct_io");
+    let file = match OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(&path)
+    {
+        Ok(file) => file,
+        // The file system doesn't support `O_DIRECT`, e.g. tmpfs.
+        Err(ref err) if err.raw_os_error() == Some(libc::EINVAL) => return,
+        Err(err) => panic!("unexpected error opening file: {}", err),
+    };
+
+    let mut aio = Context::new(8).unwrap();
+    poll.registry()
+        .register(&mut aio, ID, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = AlignedBuf([0; 4096]);
+    buf.0[..DATA.len()].copy_from_slice(DATA);
+    unsafe {
+        aio.submit_write(file.as_raw_fd(), buf.0.as_ptr(), buf.0.len(), 0, 1)
+            .unwrap();
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    let mut completions = Vec::new();
+    assert_eq!(aio.reap(&mut completions).unwrap(), 1);
+    assert_eq!(completions[0].user_data(), 1);
+    assert_eq!(completions[0].result().unwrap(), buf.0.len());
+
+    let mut buf = AlignedBuf([0; 4096]);
+    unsafe {
+        aio.submit_read(file.as_raw_fd(), buf.0.as_mut_ptr(), buf.0.len(), 0, 2)
+            .unwrap();
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    completions.clear();
+    assert_eq!(aio.reap(&mut completions).unwrap(), 1);
+    assert_eq!(completions[0].user_data(), 2);
+    assert_eq!(completions[0].result().unwrap(), buf.0.len());
+    assert_eq!(&buf.0[..DATA.len()], DATA);
+}
+
+#[test]
+fn errors_are_returned_in_completion() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("aio_errors_are_returned_in_completion");
+    // Reading from a write-only file.
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+
+    let mut aio = Context::new(8).unwrap();
+    poll.registry()
+        .register(&mut aio, ID, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 64];
+    let res = unsafe { aio.submit_read(file.as_raw_fd(), buf.as_mut_ptr(), buf.len(), 0, 1) };
+    match res {
+        // Some kernels check the file mode when submitting.
+        Err(err) => assert_eq!(err.raw_os_error(), Some(libc::EBADF)),
+        Ok(()) => {
+            expect_events(
+                &mut poll,
+                &mut events,
+                vec![ExpectEvent::new(ID, Interest::READABLE)],
+            );
+            let mut completions = Vec::new();
+            aio.reap(&mut completions).unwrap();
+            let err = completions[0].result().unwrap_err();
+            assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+        }
+    }
+}
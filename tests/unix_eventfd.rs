@@ -0,0 +1,118 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-ext",
+    feature = "net"
+))]
+
+use std::io;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+use mio::unix::EventFd;
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+const ID: Token = Token(0);
+
+#[test]
+fn eventfd() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut eventfd = EventFd::new(0).unwrap();
+    poll.registry()
+        .register(&mut eventfd, ID, Interest::READABLE)
+        .unwrap();
+    assert_would_block(eventfd.read());
+    expect_no_events(&mut poll, &mut events);
+
+    eventfd.add(1).unwrap();
+    eventfd.add(2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    assert_eq!(eventfd.read().unwrap(), 3);
+    assert_would_block(eventfd.read());
+}
+
+#[test]
+fn eventfd_initial_value() {
+    let eventfd = EventFd::new(10).unwrap();
+    assert_eq!(eventfd.read().unwrap(), 10);
+    assert_would_block(eventfd.read());
+}
+
+#[test]
+fn eventfd_semaphore() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut eventfd = EventFd::new_semaphore(2).unwrap();
+    poll.registry()
+        .register(&mut eventfd, ID, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+
+    assert_eq!(eventfd.read().unwrap(), 1);
+    assert_eq!(eventfd.read().unwrap(), 1);
+    assert_would_block(eventfd.read());
+
+    eventfd.add(1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    assert_eq!(eventfd.read().unwrap(), 1);
+}
+
+#[test]
+fn eventfd_overflow() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut eventfd = EventFd::new(0).unwrap();
+    poll.registry()
+        .register(&mut eventfd, ID, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::WRITABLE)],
+    );
+
+    eventfd.add(u64::MAX - 1).unwrap();
+    assert_would_block(eventfd.add(1));
+    let err = eventfd.add(u64::MAX).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    assert_eq!(eventfd.read().unwrap(), u64::MAX - 1);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::WRITABLE)],
+    );
+    eventfd.add(1).unwrap();
+}
+
+#[test]
+fn eventfd_raw_fd() {
+    let eventfd = EventFd::new(0).unwrap();
+    eventfd.add(5).unwrap();
+
+    let fd = eventfd.into_raw_fd();
+    let mut buf = [0u8; 8];
+    assert_eq!(
+        unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) },
+        8
+    );
+    assert_eq!(u64::from_ne_bytes(buf), 5);
+
+    let eventfd = unsafe { EventFd::from_raw_fd(fd) };
+    assert_would_block(eventfd.read());
+}
@@ -0,0 +1,87 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-ext",
+    feature = "net"
+))]
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+
+use mio::unix::{Inotify, InotifyEvent};
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, init_with_poll, temp_file, ExpectEvent};
+
+const ID: Token = Token(0);
+
+#[test]
+fn inotify() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let dir = temp_file("inotify");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut inotify = Inotify::new().unwrap();
+    let wd = inotify
+        .add_watch(&dir, libc::IN_CREATE | libc::IN_MODIFY)
+        .unwrap();
+    poll.registry()
+        .register(&mut inotify, ID, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 4096];
+    assert_would_block(inotify.read_events(&mut buf));
+
+    let mut file = File::create(dir.join("test.txt")).unwrap();
+    file.write_all(b"Hello world").unwrap();
+    file.sync_all().unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+
+    let got: Vec<InotifyEvent<'_>> = inotify.read_events(&mut buf).unwrap().collect();
+    assert!(got.len() >= 2, "{:?}", got);
+    for event in &got {
+        assert_eq!(event.wd(), wd);
+        assert_eq!(event.name().unwrap(), "test.txt");
+        assert!(!event.is_dir());
+        assert!(!event.is_overflow());
+    }
+    assert!(got[0].mask() & libc::IN_CREATE != 0);
+    assert!(got[1].mask() & libc::IN_MODIFY != 0);
+    assert_would_block(inotify.read_events(&mut buf));
+
+    // Removing the watch generates an `IN_IGNORED` event.
+    inotify.remove_watch(wd).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interest::READABLE)],
+    );
+    let mut got = inotify.read_events(&mut buf).unwrap();
+    let event = got.next().unwrap();
+    assert_eq!(event.wd(), wd);
+    assert!(event.is_ignored());
+    assert!(event.name().is_none());
+    assert!(got.next().is_none());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn inotify_raw_fd() {
+    let inotify = Inotify::new().unwrap();
+    let raw_fd1 = inotify.as_raw_fd();
+    let raw_fd2 = inotify.into_raw_fd();
+    assert_eq!(raw_fd1, raw_fd2);
+
+    let inotify = unsafe { Inotify::from_raw_fd(raw_fd2) };
+    assert_eq!(inotify.as_raw_fd(), raw_fd1);
+}
@@ -6,6 +6,8 @@ use std::sync::{Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use mio::net::UnixStream;
 use mio::unix::pipe::{self, Receiver, Sender};
 use mio::{Events, Interest, Poll, Token};
 
@@ -215,3 +217,266 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn splice_and_tee() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+    let (sender2, mut receiver2) = pipe::new().unwrap();
+    let (mut src, src_peer) = UnixStream::pair().unwrap();
+    let (dst, mut dst_peer) = UnixStream::pair().unwrap();
+
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+
+    assert_would_block(sender.splice_from(&src_peer, 64));
+    src.write_all(DATA1).unwrap();
+    assert_eq!(sender.splice_from(&src_peer, 64).unwrap(), DATA1.len());
+    assert_would_block(sender.splice_from(&src_peer, 64));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    // `tee` doesn't consume the data.
+    assert_eq!(receiver.tee(&sender2, 64).unwrap(), DATA1.len());
+    assert_eq!(receiver.splice_to(&dst, 64).unwrap(), DATA1.len());
+    assert_would_block(receiver.splice_to(&dst, 64));
+
+    let mut buf = [0; 20];
+    let n = dst_peer.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let n = receiver2.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // End of file.
+    drop(src);
+    assert_eq!(sender.splice_from(&src_peer, 64).unwrap(), 0);
+    drop(sender);
+    assert_eq!(receiver.splice_to(&dst, 64).unwrap(), 0);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn copy_bidirectional() {
+    use std::net::Shutdown;
+    use std::os::unix::net;
+
+    const A: Token = Token(0);
+    const B: Token = Token(1);
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (a1, a2) = net::UnixStream::pair().unwrap();
+    let (b1, b2) = net::UnixStream::pair().unwrap();
+    a2.set_nonblocking(true).unwrap();
+    b1.set_nonblocking(true).unwrap();
+    let mut a2 = UnixStream::from_std(a2);
+    let mut b1 = UnixStream::from_std(b1);
+    poll.registry()
+        .register(&mut a2, A, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut b1, B, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+
+    // More data than fits in the intermediate pipes and socket buffers.
+    let data_a: Vec<u8> = (0..1024 * 1024).map(|i| i as u8).collect();
+    let data_b: Vec<u8> = (0..512 * 1024).map(|i| (i * 3) as u8).collect();
+    let peer = |mut stream: net::UnixStream, data: Vec<u8>| {
+        thread::spawn(move || {
+            let mut s = stream.try_clone().unwrap();
+            let writer = thread::spawn(move || {
+                s.write_all(&data).unwrap();
+                s.shutdown(Shutdown::Write).unwrap();
+            });
+            let mut received = Vec::new();
+            stream.read_to_end(&mut received).unwrap();
+            writer.join().unwrap();
+            received
+        })
+    };
+    let peer_a = peer(a1, data_a.clone());
+    let peer_b = peer(b2, data_b.clone());
+
+    let mut copy = pipe::CopyBidirectional::new().unwrap();
+    while !copy.copy(&a2, &b1).unwrap() {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events");
+    }
+    assert_eq!(copy.a_to_b(), data_a.len() as u64);
+    assert_eq!(copy.b_to_a(), data_b.len() as u64);
+
+    assert!(peer_b.join().unwrap() == data_a);
+    assert!(peer_a.join().unwrap() == data_b);
+}
+
+#[test]
+fn pending_bytes() {
+    let (mut sender, mut receiver) = pipe::Builder::new().build().unwrap();
+    assert_eq!(receiver.pending_bytes().unwrap(), 0);
+
+    sender.write_all(DATA1).unwrap();
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(sender.pending_bytes().unwrap(), DATA1.len());
+    assert_eq!(receiver.pending_bytes().unwrap(), DATA1.len());
+
+    let mut buf = [0; 4];
+    receiver.read_exact(&mut buf).unwrap();
+    assert_eq!(receiver.pending_bytes().unwrap(), DATA1.len() - 4);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn builder_capacity() {
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+
+    let (sender, receiver) = pipe::new().unwrap();
+    let default = sender.capacity().unwrap();
+    assert_eq!(receiver.capacity().unwrap(), default);
+
+    let (mut sender, _receiver) = pipe::Builder::new().capacity(page_size).build().unwrap();
+    assert_eq!(sender.capacity().unwrap(), page_size);
+
+    // Fill the pipe.
+    let data = vec![1; page_size * 2];
+    let n = sender.write(&data).unwrap();
+    assert_eq!(n, page_size);
+    assert_would_block(sender.write(&data));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn builder_packet_mode() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut sender, mut receiver) = pipe::Builder::new().packet_mode(true).build().unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+
+    sender.write_all(DATA1).unwrap();
+    sender.write_all(b"abc").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+
+    // Each read returns a single packet.
+    let mut buf = [0; 20];
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"abc");
+    assert_would_block(receiver.read(&mut buf));
+}
+
+#[test]
+fn readiness() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+
+    let mut buf = [0; 4096];
+    assert_would_block(receiver.read(&mut buf));
+    assert_eq!(receiver.readiness(), Some(Interest::WRITABLE));
+
+    // Fill the pipe.
+    let mut written = 0;
+    loop {
+        match sender.write(&buf) {
+            Ok(n) => written += n,
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert_eq!(sender.readiness(), Some(Interest::READABLE));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    assert_eq!(
+        receiver.readiness(),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+
+    while written > 0 {
+        written -= receiver.read(&mut buf).unwrap();
+    }
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+    assert_eq!(
+        sender.readiness(),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+}
+
+#[test]
+fn readiness_reregister() {
+    const OTHER: Token = Token(2);
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    let mut buf = [0; 16];
+    assert_would_block(receiver.read(&mut buf));
+
+    // The readiness should follow the new token.
+    poll.registry()
+        .reregister(&mut receiver, OTHER, Interest::READABLE)
+        .unwrap();
+    sender.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(OTHER, Interest::READABLE)],
+    );
+    assert_eq!(
+        receiver.readiness(),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_would_block(receiver.read(&mut buf));
+
+    // Once deregistered events are no longer received, so the readiness
+    // stays cleared.
+    poll.registry().deregister(&mut receiver).unwrap();
+    sender.write_all(DATA1).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(receiver.readiness(), Some(Interest::WRITABLE));
+}
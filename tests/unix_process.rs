@@ -0,0 +1,105 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::io::{Read, Write};
+use std::process::Stdio;
+
+use mio::unix::process::Command;
+use mio::{Interest, Token};
+
+mod util;
+use util::{expect_events, init_with_poll, ExpectEvent};
+
+const STDIN: Token = Token(0);
+const STDOUT: Token = Token(1);
+const EXIT: Token = Token(2);
+
+const DATA: &[u8] = b"Hello child process!";
+
+#[test]
+fn process_pipes() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("cat").spawn().unwrap();
+    let mut stdin = child.take_stdin().unwrap();
+    let mut stdout = child.take_stdout().unwrap();
+    assert!(child.take_stderr().is_some());
+    assert!(child.take_stdin().is_none());
+
+    poll.registry()
+        .register(&mut stdin, STDIN, Interest::WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stdout, STDOUT, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut child, EXIT, Interest::READABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(STDIN, Interest::WRITABLE)],
+    );
+    stdin.write_all(DATA).unwrap();
+    drop(stdin);
+
+    // `cat` exits once its input is closed, possibly before we poll, so both
+    // events can be returned at the same time.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(STDOUT, Interest::READABLE),
+            ExpectEvent::new(EXIT, Interest::READABLE),
+        ],
+    );
+    let mut buf = [0; 64];
+    let n = stdout.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA);
+
+    let status = child.try_wait().unwrap().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn process_exit() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("sh")
+        .args(["-c", "exit 3"])
+        .stdin(Stdio::null())
+        .spawn()
+        .unwrap();
+    assert!(child.take_stdin().is_none());
+    poll.registry()
+        .register(&mut child, EXIT, Interest::READABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(EXIT, Interest::READABLE)],
+    );
+    let status = child.try_wait().unwrap().unwrap();
+    assert_eq!(status.code(), Some(3));
+}
+
+#[test]
+fn process_kill() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+    poll.registry()
+        .register(&mut child, EXIT, Interest::READABLE)
+        .unwrap();
+    assert!(child.try_wait().unwrap().is_none());
+
+    child.kill().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(EXIT, Interest::READABLE)],
+    );
+    let status = child.try_wait().unwrap().unwrap();
+    assert!(!status.success());
+}
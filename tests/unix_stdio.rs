@@ -0,0 +1,164 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use mio::unix::stdio;
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{init, temp_file};
+
+const STDIN: Token = Token(0);
+const STDOUT: Token = Token(1);
+
+/// Environment variable set when running `stdio_child`, set to whether or not
+/// a helper thread is expected to be used.
+const CHILD_ENV: &str = "MIO_STDIO_CHILD";
+
+const DATA: &[u8] = b"Hello from the standard input!";
+
+/// Runs `stdio_child`, which copies its standard input to its standard
+/// output.
+fn child(stdin: Stdio, stdout: Stdio, helper_thread: bool) -> Command {
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(["--exact", "stdio_child", "--ignored", "--nocapture"])
+        .env(CHILD_ENV, if helper_thread { "1" } else { "0" })
+        .stdin(stdin)
+        .stdout(stdout);
+    command
+}
+
+#[test]
+fn stdio_pipes() {
+    init();
+
+    // Pipes can be reopened on Linux.
+    let helper_thread = !cfg!(any(target_os = "android", target_os = "linux"));
+    let mut child = child(Stdio::piped(), Stdio::piped(), helper_thread)
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(DATA).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "child failed: {}", output.status);
+    assert!(
+        contains(&output.stdout, DATA),
+        "unexpected output: {:?}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn stdio_files() {
+    init();
+
+    let input = temp_file("stdio_files_input");
+    fs::write(&input, DATA).unwrap();
+    let output = temp_file("stdio_files_output");
+
+    // Regular files always use a helper thread.
+    let status = child(
+        File::open(&input).unwrap().into(),
+        File::create(&output).unwrap().into(),
+        true,
+    )
+    .status()
+    .unwrap();
+    assert!(status.success(), "child failed: {}", status);
+
+    let output = fs::read(&output).unwrap();
+    assert!(
+        contains(&output, DATA),
+        "unexpected output: {:?}",
+        String::from_utf8_lossy(&output)
+    );
+}
+
+#[test]
+#[ignore = "run by the `stdio_*` tests"]
+fn stdio_child() {
+    let helper_thread = match env::var(CHILD_ENV) {
+        Ok(value) => value == "1",
+        Err(_) => return,
+    };
+
+    // Not using `init_with_poll` as that removes the temporary files used by
+    // the parent test.
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut stdin = stdio::stdin().unwrap();
+    let mut stdout = stdio::stdout().unwrap();
+    assert_eq!(stdin.uses_helper_thread(), helper_thread);
+    assert_eq!(stdout.uses_helper_thread(), helper_thread);
+    // Additional handles share the helper threads.
+    #[cfg(target_os = "linux")]
+    let threads = thread_count();
+    drop(stdio::stdin().unwrap());
+    drop(stdio::stdout().unwrap());
+    // The stdin helper thread can stop once it reaches end of file.
+    #[cfg(target_os = "linux")]
+    assert!(thread_count() <= threads);
+    // The shared file descriptions should not be changed.
+    assert!(!is_nonblocking(0));
+    assert!(!is_nonblocking(1));
+
+    poll.registry()
+        .register(&mut stdin, STDIN, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stdout, STDOUT, Interest::WRITABLE)
+        .unwrap();
+
+    let mut pending = Vec::new();
+    let mut eof = false;
+    while !eof || !pending.is_empty() {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events");
+
+        let mut buf = [0; 64];
+        while !eof {
+            match stdin.read(&mut buf) {
+                Ok(0) => eof = true,
+                Ok(n) => pending.extend_from_slice(&buf[..n]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error reading: {}", err),
+            }
+        }
+
+        while !pending.is_empty() {
+            match stdout.write(&pending) {
+                Ok(n) => drop(pending.drain(..n)),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error writing: {}", err),
+            }
+        }
+    }
+    // The process exits once the test is done, possibly before the helper
+    // thread wrote everything.
+    assert!(stdout.wait_written(Some(Duration::from_secs(5))));
+}
+
+/// Returns the number of threads in this process.
+#[cfg(target_os = "linux")]
+fn thread_count() -> usize {
+    fs::read_dir("/proc/self/task").unwrap().count()
+}
+
+fn is_nonblocking(fd: libc::c_int) -> bool {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    assert!(flags != -1, "{}", io::Error::last_os_error());
+    flags & libc::O_NONBLOCK != 0
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
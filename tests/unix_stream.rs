@@ -543,3 +543,66 @@ fn new_noop_listener(
     });
     (handle, receiver.recv().unwrap())
 }
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "watchos",
+))]
+fn unix_stream_sendfile() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Larger than the socket buffers, so that `sendfile` blocks.
+    let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let path = temp_file("unix_stream_sendfile");
+    std::fs::write(&path, &data).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let (mut stream, peer) = UnixStream::pair().unwrap();
+    let mut peer = Some(peer);
+    poll.registry()
+        .register(&mut stream, TOKEN_1, Interest::WRITABLE)
+        .unwrap();
+
+    // Skip the first bytes to test the offset.
+    const START: usize = 10;
+    let mut offset = START;
+    let mut reader = None;
+    while offset < data.len() {
+        match stream.sendfile(&file, offset as u64, data.len() - offset) {
+            Ok(n) => {
+                assert_ne!(n, 0);
+                offset += n;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                // Only start reading once the buffers are full.
+                if let Some(mut peer) = peer.take() {
+                    reader = Some(thread::spawn(move || {
+                        let mut received = Vec::new();
+                        peer.read_to_end(&mut received).unwrap();
+                        received
+                    }));
+                }
+                expect_events(
+                    &mut poll,
+                    &mut events,
+                    vec![ExpectEvent::new(TOKEN_1, Interest::WRITABLE)],
+                );
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    // At the end of the file.
+    assert_eq!(stream.sendfile(&file, offset as u64, 100).unwrap(), 0);
+    assert_eq!(stream.sendfile(&file, 0, 0).unwrap(), 0);
+    drop(stream);
+
+    let received = reader.expect("sendfile never blocked").join().unwrap();
+    assert!(received == data[START..], "received different data");
+}
@@ -0,0 +1,164 @@
+#![cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "os-poll",
+    feature = "os-ext",
+    feature = "net"
+))]
+
+use std::env;
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use mio::unix::Tty;
+use mio::{Interest, Token};
+
+mod util;
+use util::init_with_poll;
+
+const ID: Token = Token(0);
+
+/// Environment variable set when running `tty_child`.
+const CHILD_ENV: &str = "MIO_TTY_CHILD";
+
+/// The test process doesn't have a controlling terminal, so this runs
+/// `tty_child` in a new session using a pseudoterminal.
+#[test]
+fn tty() {
+    let master = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC) };
+    assert!(master != -1, "{}", io::Error::last_os_error());
+    let mut master = unsafe { File::from_raw_fd(master) };
+    assert_eq!(unsafe { libc::grantpt(master.as_raw_fd()) }, 0);
+    assert_eq!(unsafe { libc::unlockpt(master.as_raw_fd()) }, 0);
+    let slave_path = unsafe { CStr::from_ptr(libc::ptsname(master.as_raw_fd())) };
+    let slave = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(slave_path.to_str().unwrap())
+        .unwrap();
+
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(["--exact", "tty_child", "--ignored", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .stdin(Stdio::from(slave));
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = command.spawn().unwrap();
+
+    // Wait until the child is ready.
+    let mut buf = Vec::new();
+    let mut read_buf = [0; 64];
+    while !buf.ends_with(b"ready") {
+        match master.read(&mut read_buf) {
+            Ok(n) => buf.extend_from_slice(&read_buf[..n]),
+            // Child exited.
+            Err(_) => break,
+        }
+    }
+
+    // Without a newline the input is only readable in raw mode.
+    master.write_all(b"hello").unwrap();
+    let size = libc::winsize {
+        ws_row: 24,
+        ws_col: 80,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    assert_eq!(
+        unsafe { libc::ioctl(master.as_raw_fd(), libc::TIOCSWINSZ, &size) },
+        0
+    );
+
+    let status = child.wait().unwrap();
+    assert!(status.success(), "child failed: {}", status);
+}
+
+#[test]
+#[ignore = "run by the `tty` test"]
+fn tty_child() {
+    if env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut tty = Tty::open().unwrap();
+    assert!(!tty.is_raw_mode());
+    tty.set_raw_mode().unwrap();
+    assert!(tty.is_raw_mode());
+    assert!(!is_canonical(&tty));
+
+    poll.registry()
+        .register(&mut tty, ID, Interest::READABLE)
+        .unwrap();
+    tty.write_all(b"ready").unwrap();
+
+    let mut input = Vec::new();
+    let mut size = None;
+    while input != b"hello" || size.is_none() {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events, input: {:?}", input);
+        for event in events.iter() {
+            assert_eq!(event.token(), ID);
+        }
+
+        if let Some(s) = tty.take_resize().unwrap() {
+            size = Some(s);
+        }
+        let mut buf = [0; 64];
+        loop {
+            match tty.read(&mut buf) {
+                Ok(n) => input.extend_from_slice(&buf[..n]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+    }
+
+    let size = size.unwrap();
+    assert_eq!(size.rows(), 24);
+    assert_eq!(size.columns(), 80);
+    assert_eq!(tty.window_size().unwrap(), size);
+    assert!(tty.take_resize().unwrap().is_none());
+
+    // Without readable interest resizes are not reported.
+    poll.registry()
+        .reregister(&mut tty, ID, Interest::WRITABLE)
+        .unwrap();
+    assert_eq!(unsafe { libc::raise(libc::SIGWINCH) }, 0);
+    for _ in 0..3 {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        for event in events.iter() {
+            assert!(!event.is_readable(), "unexpected event: {:?}", event);
+        }
+    }
+    // But they can still be checked.
+    assert_eq!(tty.take_resize().unwrap(), Some(size));
+
+    // Dropping should restore the original settings.
+    drop(tty);
+    let tty = Tty::open().unwrap();
+    assert!(is_canonical(&tty));
+}
+
+fn is_canonical(tty: &Tty) -> bool {
+    let mut settings: libc::termios = unsafe { std::mem::zeroed() };
+    assert_eq!(
+        unsafe { libc::tcgetattr(tty.as_raw_fd(), &mut settings) },
+        0
+    );
+    settings.c_lflag & libc::ICANON != 0
+}
@@ -130,6 +130,12 @@ impl From<Interest> for Readiness {
         if interests.is_lio() {
             readiness.0 |= LIO;
         }
+        if interests.is_read_closed() {
+            readiness.0 |= READ_CLOSED;
+        }
+        if interests.is_error() {
+            readiness.0 |= ERROR;
+        }
         readiness
     }
 }
@@ -0,0 +1,139 @@
+#![cfg(all(target_os = "linux", feature = "os-poll", feature = "net"))]
+
+use std::io::{self, Read, Write};
+use std::net;
+use std::time::Duration;
+
+use mio::net::{TcpStream, UdpSocket, ZerocopyCompletion};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{any_local_address, expect_events, init_with_poll, ExpectEvent};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const DATA1: &[u8] = b"Hello world!";
+const DATA2: &[u8] = b"Hello mars!";
+const DATA3: &[u8] = b"Hello moon!";
+
+#[test]
+fn tcp_stream() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    assert!(!stream.zerocopy().unwrap());
+    stream.set_zerocopy(true).unwrap();
+    assert!(stream.zerocopy().unwrap());
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    for data in [DATA1, DATA2, DATA3].iter() {
+        assert_eq!(stream.send_zerocopy(data).unwrap(), data.len());
+    }
+    let completions = expect_completions(&mut poll, &mut events, ID1, 3, || {
+        stream.recv_zerocopy_completion()
+    });
+    // Sending over loopback always copies.
+    assert!(completions.iter().all(ZerocopyCompletion::copied));
+    assert!(stream.take_error().unwrap().is_none());
+
+    let mut buf = [0; 34];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], [DATA1, DATA2, DATA3].concat().as_slice());
+
+    // Receiving the completions doesn't affect the normal readiness.
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 12];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, DATA1);
+    assert_eq!(stream.send_zerocopy(DATA2).unwrap(), DATA2.len());
+}
+
+#[test]
+fn udp_socket() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let peer = net::UdpSocket::bind(any_local_address()).unwrap();
+    let peer_addr = peer.local_addr().unwrap();
+
+    socket.set_zerocopy(true).unwrap();
+    assert!(socket.zerocopy().unwrap());
+    poll.registry()
+        .register(&mut socket, ID2, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(
+        socket.send_to_zerocopy(DATA1, peer_addr).unwrap(),
+        DATA1.len()
+    );
+    socket.connect(peer_addr).unwrap();
+    assert_eq!(socket.send_zerocopy(DATA2).unwrap(), DATA2.len());
+    expect_completions(&mut poll, &mut events, ID2, 2, || {
+        socket.recv_zerocopy_completion()
+    });
+
+    let mut buf = [0; 16];
+    let n = peer.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let n = peer.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+}
+
+/// Receive completions for the sends with IDs `0..sends`, waiting for error
+/// events.
+fn expect_completions<F>(
+    poll: &mut Poll,
+    events: &mut Events,
+    token: Token,
+    sends: u32,
+    mut recv: F,
+) -> Vec<ZerocopyCompletion>
+where
+    F: FnMut() -> io::Result<ZerocopyCompletion>,
+{
+    let mut completions = Vec::new();
+    let mut completed = 0;
+    for _ in 0..10 {
+        poll.poll(events, Some(Duration::from_millis(500))).unwrap();
+        if !events
+            .iter()
+            .any(|event| event.token() == token && event.is_error())
+        {
+            continue;
+        }
+
+        loop {
+            match recv() {
+                Ok(completion) => {
+                    // Completions are reported in order.
+                    assert!(completion.contains(completed), "{:?}", completion);
+                    completed = completion.end().wrapping_add(1);
+                    completions.push(completion);
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+        if completed == sends {
+            return completions;
+        }
+    }
+    panic!("only {} of {} sends completed", completed, sends);
+}